@@ -2639,6 +2639,7 @@ pub async fn run(
     let observer: Arc<dyn Observer> = Arc::new(
         crate::plugins::bridge::observer::ObserverBridge::new(base_observer),
     );
+    crate::plugins::runtime::register_observer(&observer);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -2647,8 +2648,10 @@ pub async fn run(
     ));
 
     // ── Memory (the brain) ────────────────────────────────────────
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_plugins(
         &config.memory,
+        &config.plugins,
+        &[],
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
@@ -3369,14 +3372,17 @@ pub async fn process_message_with_session(
     let observer: Arc<dyn Observer> = Arc::new(
         crate::plugins::bridge::observer::ObserverBridge::new(base_observer),
     );
+    crate::plugins::runtime::register_observer(&observer);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
         &config.autonomy,
         &config.workspace_dir,
     ));
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_plugins(
         &config.memory,
+        &config.plugins,
+        &[],
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
@@ -5467,6 +5467,7 @@ pub async fn start_channels(config: Config) -> Result<()> {
     let observer: Arc<dyn Observer> = Arc::new(
         crate::plugins::bridge::observer::ObserverBridge::new(base_observer),
     );
+    crate::plugins::runtime::register_observer(&observer);
     let runtime: Arc<dyn runtime::RuntimeAdapter> =
         Arc::from(runtime::create_runtime(&config.runtime)?);
     let security = Arc::new(SecurityPolicy::from_config(
@@ -5474,8 +5475,10 @@ pub async fn start_channels(config: Config) -> Result<()> {
         &config.workspace_dir,
     ));
     let temperature = config.default_temperature;
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_plugins(
         &config.memory,
+        &config.plugins,
+        &[],
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
@@ -3000,7 +3000,7 @@ pub struct MemoryConfig {
     pub backend: String,
     /// Auto-save user-stated conversation input to memory (assistant output is excluded)
     pub auto_save: bool,
-    /// Run memory/session hygiene (archiving + retention cleanup)
+    /// Run memory/session hygiene (archiving, retention cleanup, and markdown compaction)
     #[serde(default = "default_hygiene_enabled")]
     pub hygiene_enabled: bool,
     /// Archive daily/session files older than this many days
@@ -3302,12 +3302,37 @@ pub struct PluginsConfig {
     /// Per-plugin configuration entries.
     #[serde(default)]
     pub entries: std::collections::HashMap<String, PluginEntryConfig>,
+
+    /// Upper bound on the number of plugins registered from `load_paths`.
+    /// Manifests are taken in path order; those beyond the cap are skipped
+    /// with a single summary warning.
+    /// Default: `256`.
+    #[serde(default = "default_plugins_max_plugins")]
+    pub max_plugins: usize,
+
+    /// Re-scan `load_paths` for changed manifests before routing plugin calls.
+    /// Changed modules are always recompiled on next use. Default: `false`.
+    #[serde(default)]
+    pub hot_reload: bool,
+
+    /// Directory holding each plugin's `zeroclaw_state_*` key-value file
+    /// (`<state_dir>/<plugin id>.json`). Default: `~/.zeroclaw/plugin-state`.
+    #[serde(default = "default_plugins_state_dir")]
+    pub state_dir: String,
 }
 
 fn default_plugins_enabled() -> bool {
     true
 }
 
+fn default_plugins_state_dir() -> String {
+    "~/.zeroclaw/plugin-state".to_string()
+}
+
+fn default_plugins_max_plugins() -> usize {
+    256
+}
+
 impl Default for PluginsConfig {
     fn default() -> Self {
         Self {
@@ -3316,6 +3341,9 @@ impl Default for PluginsConfig {
             deny: Vec::new(),
             load_paths: Vec::new(),
             entries: std::collections::HashMap::new(),
+            max_plugins: default_plugins_max_plugins(),
+            hot_reload: false,
+            state_dir: default_plugins_state_dir(),
         }
     }
 }
@@ -3330,6 +3358,71 @@ pub struct PluginEntryConfig {
     /// Plugin-specific configuration table, passed to `PluginApi::plugin_config()`.
     #[serde(default)]
     pub config: serde_json::Value,
+
+    /// Maximum number of concurrent calls into this plugin's module, applied on
+    /// top of the global plugin concurrency limit. Set to `1` to serialize
+    /// access for plugins backed by a single-connection resource.
+    /// If absent, only the global limit applies.
+    #[serde(default)]
+    pub max_concurrent_calls: Option<usize>,
+
+    /// Wall-clock budget for a single call into this plugin, in milliseconds.
+    /// Guest code is interrupted once it elapses. If absent, the runtime
+    /// default applies.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+
+    /// Hex-encoded SHA-256 the plugin's module bytes must match. Checked when
+    /// the registry is loaded; a mismatch refuses this plugin only.
+    #[serde(default)]
+    pub sha256: Option<String>,
+
+    /// Capabilities this plugin may declare (e.g. `["Tools"]`). A manifest
+    /// declaring anything outside the list is refused at load; other plugins
+    /// are unaffected. If absent, any declared capability is accepted.
+    #[serde(default)]
+    pub allowed_capabilities: Option<Vec<String>>,
+
+    /// Extra attempts for calls that fail transiently: the module file could
+    /// not be read, or the call timed out. Timeouts are only retried when the
+    /// plugin's manifest declares `idempotent = true`, since a timed-out guest
+    /// may already have had side effects. If absent, calls are not retried.
+    #[serde(default)]
+    pub retry_attempts: Option<u32>,
+
+    /// Delay before the first retry, doubled for each further retry.
+    /// Default: `100`.
+    #[serde(default)]
+    pub retry_backoff_ms: Option<u64>,
+
+    /// Quarantine the plugin after this many timed-out calls within
+    /// `quarantine_window_secs`: it is dropped from routing, so callers fall
+    /// back as if it were not installed. If absent, timeouts never quarantine.
+    #[serde(default)]
+    pub quarantine_after_timeouts: Option<u32>,
+
+    /// Rolling window for `quarantine_after_timeouts`, in seconds.
+    /// Default: `300`.
+    #[serde(default)]
+    pub quarantine_window_secs: Option<u64>,
+
+    /// Re-enable a quarantined plugin automatically after this many seconds.
+    /// If absent, it stays quarantined until re-enabled explicitly.
+    #[serde(default)]
+    pub quarantine_cooldown_secs: Option<u64>,
+
+    /// For a memory plugin used as a `plugin:<id>` tier: whether other tiers
+    /// may answer reads while the plugin fails. Set to `false` so reads fail
+    /// instead of serving possibly stale data; writes are unaffected.
+    /// Default: `true`.
+    #[serde(default)]
+    pub read_fallback: Option<bool>,
+
+    /// For a memory plugin: categories it may store (e.g. `["conversation"]`).
+    /// Stores in any other category are refused by the host. If absent, every
+    /// category is allowed.
+    #[serde(default)]
+    pub allowed_categories: Option<Vec<String>>,
 }
 
 impl Default for PluginEntryConfig {
@@ -3337,6 +3430,17 @@ impl Default for PluginEntryConfig {
         Self {
             enabled: None,
             config: serde_json::Value::Object(serde_json::Map::new()),
+            max_concurrent_calls: None,
+            timeout_ms: None,
+            sha256: None,
+            allowed_capabilities: None,
+            retry_attempts: None,
+            retry_backoff_ms: None,
+            quarantine_after_timeouts: None,
+            quarantine_window_secs: None,
+            quarantine_cooldown_secs: None,
+            read_fallback: None,
+            allowed_categories: None,
         }
     }
 }
@@ -8771,6 +8875,18 @@ impl Config {
             validate_mcp_config(&self.mcp)?;
         }
 
+        // Plugins
+        for (id, entry) in &self.plugins.entries {
+            if entry.max_concurrent_calls == Some(0) {
+                anyhow::bail!("plugins.entries.{id}.max_concurrent_calls must be greater than 0");
+            }
+            if entry.quarantine_after_timeouts == Some(0) {
+                anyhow::bail!(
+                    "plugins.entries.{id}.quarantine_after_timeouts must be greater than 0"
+                );
+            }
+        }
+
         // Proxy (delegate to existing validation)
         self.proxy.validate()?;
 
@@ -12139,6 +12255,44 @@ default_temperature = 0.7
         assert!(error.to_string().contains("browser.browser_open"));
     }
 
+    #[test]
+    async fn config_validate_rejects_zero_plugin_concurrency() {
+        let mut config = Config::default();
+        config.plugins.entries.insert(
+            "demo".into(),
+            PluginEntryConfig {
+                max_concurrent_calls: Some(0),
+                ..PluginEntryConfig::default()
+            },
+        );
+
+        let error = config
+            .validate()
+            .expect_err("expected plugins max_concurrent_calls validation failure");
+        assert!(error
+            .to_string()
+            .contains("plugins.entries.demo.max_concurrent_calls"));
+    }
+
+    #[test]
+    async fn config_validate_rejects_zero_plugin_quarantine_threshold() {
+        let mut config = Config::default();
+        config.plugins.entries.insert(
+            "demo".into(),
+            PluginEntryConfig {
+                quarantine_after_timeouts: Some(0),
+                ..PluginEntryConfig::default()
+            },
+        );
+
+        let error = config
+            .validate()
+            .expect_err("expected plugins quarantine_after_timeouts validation failure");
+        assert!(error
+            .to_string()
+            .contains("plugins.entries.demo.quarantine_after_timeouts"));
+    }
+
     #[test]
     async fn config_validate_rejects_unknown_browser_backend_value() {
         let mut config = Config::default();
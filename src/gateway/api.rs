@@ -516,7 +516,7 @@ pub async fn handle_api_cli_tools(
     Json(serde_json::json!({"cli_tools": tools})).into_response()
 }
 
-/// GET /api/health — component health snapshot
+/// GET /api/health — component health snapshot plus per-plugin health
 pub async fn handle_api_health(
     State(state): State<AppState>,
     headers: HeaderMap,
@@ -526,7 +526,10 @@ pub async fn handle_api_health(
     }
 
     let snapshot = crate::health::snapshot();
-    Json(serde_json::json!({"health": snapshot})).into_response()
+    let plugins = crate::plugins::runtime::PluginRuntime::new()
+        .health_report()
+        .await;
+    Json(serde_json::json!({"health": snapshot, "plugins": plugins})).into_response()
 }
 
 /// GET /api/pairing/devices — list paired devices
@@ -402,6 +402,13 @@ pub struct AppState {
 pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     if let Err(error) = crate::plugins::runtime::initialize_from_config(&config.plugins) {
         tracing::warn!("plugin registry initialization skipped: {error}");
+    } else {
+        // Warm the module cache off the async runtime so first calls skip compilation.
+        tokio::task::spawn_blocking(|| {
+            if let Err(error) = crate::plugins::runtime::precompile_modules() {
+                tracing::warn!("plugin module precompile skipped: {error}");
+            }
+        });
     }
 
     // ── Security: refuse public bind without tunnel or explicit opt-in ──
@@ -449,8 +456,10 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
         .clone()
         .unwrap_or_else(|| "anthropic/claude-sonnet-4".into());
     let temperature = config.default_temperature;
-    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_storage(
+    let mem: Arc<dyn Memory> = Arc::from(memory::create_memory_with_plugins(
         &config.memory,
+        &config.plugins,
+        &[],
         Some(&config.storage.provider.config),
         &config.workspace_dir,
         config.api_key.as_deref(),
@@ -788,6 +797,7 @@ pub async fn run_gateway(host: &str, port: u16, config: Config) -> Result<()> {
     let broadcast_observer: Arc<dyn crate::observability::Observer> = Arc::new(
         sse::BroadcastObserver::new(Box::new(bridged_observer), event_tx.clone()),
     );
+    crate::plugins::runtime::register_observer(&broadcast_observer);
 
     let state = AppState {
         config: config_state,
@@ -972,6 +982,16 @@ async fn handle_metrics(
         );
     }
 
+    // Plugin in-flight counts are sampled at scrape time.
+    for (plugin, calls) in crate::plugins::runtime::plugin_in_flight_calls() {
+        state.observer.record_metric(
+            &crate::observability::traits::ObserverMetric::PluginInFlightCalls {
+                plugin,
+                calls: u64::try_from(calls).unwrap_or(u64::MAX),
+            },
+        );
+    }
+
     let body = if let Some(prom) = state
         .observer
         .as_ref()
@@ -382,6 +382,15 @@ pub enum MemoryCommands {
     },
 }
 
+/// Plugin management subcommands
+#[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub enum PluginCommands {
+    /// Show every discovered plugin and whether its module is healthy
+    Status,
+    /// Check every enabled plugin's module without running it
+    Validate,
+}
+
 /// Integration subcommands
 #[derive(Subcommand, Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum IntegrationCommands {
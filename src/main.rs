@@ -103,7 +103,7 @@ use config::Config;
 // Re-export so binary modules can use crate::<CommandEnum> while keeping a single source of truth.
 pub use zeroclaw::{
     ChannelCommands, CronCommands, HardwareCommands, IntegrationCommands, MigrateCommands,
-    PeripheralCommands, ServiceCommands, SkillCommands,
+    PeripheralCommands, PluginCommands, ServiceCommands, SkillCommands,
 };
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq, ValueEnum)]
@@ -475,6 +475,19 @@ Examples:
         skill_command: SkillCommands,
     },
 
+    /// Inspect WASM plugins
+    #[command(long_about = "\
+Inspect WASM plugins loaded from [plugins] load_paths.
+
+Examples:
+  zeroclaw plugins status
+  zeroclaw plugins validate")]
+    #[command(name = "plugins", alias = "plugin")]
+    Plugins {
+        #[command(subcommand)]
+        plugin_command: PluginCommands,
+    },
+
     /// Migrate data from other agent runtimes
     Migrate {
         #[command(subcommand)]
@@ -1276,6 +1289,10 @@ async fn main() -> Result<()> {
 
         Commands::Skills { skill_command } => skills::handle_command(skill_command, &config),
 
+        Commands::Plugins { plugin_command } => {
+            plugins::cli::handle_command(plugin_command, &config).await
+        }
+
         Commands::Migrate { migrate_command } => {
             migration::handle_command(migrate_command, &config).await
         }
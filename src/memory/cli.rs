@@ -307,7 +307,14 @@ async fn handle_reindex(config: &Config, yes: bool, progress: bool) -> Result<()
     use std::sync::Arc;
 
     // Reindex requires full memory backend with embeddings
-    let mem = super::create_memory(&config.memory, &config.workspace_dir, None)?;
+    let mem = super::create_memory_with_plugins(
+        &config.memory,
+        &config.plugins,
+        &[],
+        None,
+        &config.workspace_dir,
+        None,
+    )?;
 
     // Get total count for confirmation
     let total = mem.count().await?;
@@ -1,3 +1,5 @@
+use super::backend::{classify_memory_backend, MemoryBackendKind};
+use super::MarkdownMemory;
 use crate::config::MemoryConfig;
 use anyhow::Result;
 use chrono::{DateTime, Duration, Local, NaiveDate, Utc};
@@ -17,6 +19,10 @@ struct HygieneReport {
     purged_memory_archives: u64,
     purged_session_archives: u64,
     pruned_conversation_rows: u64,
+    #[serde(default)]
+    compacted_markdown_entries: u64,
+    #[serde(default)]
+    failed_markdown_compactions: u64,
 }
 
 impl HygieneReport {
@@ -26,6 +32,7 @@ impl HygieneReport {
             + self.purged_memory_archives
             + self.purged_session_archives
             + self.pruned_conversation_rows
+            + self.compacted_markdown_entries
     }
 }
 
@@ -47,6 +54,8 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
         return Ok(());
     }
 
+    let (compacted_markdown_entries, failed_markdown_compactions) =
+        compact_markdown_memory(config, workspace_dir);
     let report = HygieneReport {
         archived_memory_files: archive_daily_memory_files(
             workspace_dir,
@@ -59,18 +68,22 @@ pub fn run_if_due(config: &MemoryConfig, workspace_dir: &Path) -> Result<()> {
             workspace_dir,
             config.conversation_retention_days,
         )?,
+        compacted_markdown_entries,
+        failed_markdown_compactions,
     };
 
     write_state(workspace_dir, &report)?;
 
-    if report.total_actions() > 0 {
+    if report.total_actions() > 0 || report.failed_markdown_compactions > 0 {
         tracing::info!(
-            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={}",
+            "memory hygiene complete: archived_memory={} archived_sessions={} purged_memory={} purged_sessions={} pruned_conversation_rows={} compacted_markdown_entries={} failed_markdown_compactions={}",
             report.archived_memory_files,
             report.archived_session_files,
             report.purged_memory_archives,
             report.purged_session_archives,
             report.pruned_conversation_rows,
+            report.compacted_markdown_entries,
+            report.failed_markdown_compactions,
         );
     }
 
@@ -318,6 +331,23 @@ fn prune_conversation_rows(workspace_dir: &Path, retention_days: u32) -> Result<
     Ok(u64::try_from(affected).unwrap_or(0))
 }
 
+/// Drop superseded entries from the markdown backend's files, returning the
+/// entries dropped and the failed compactions. Other backends keep their own
+/// storage, so `MEMORY.md` is left alone for them. A failure is logged and
+/// counted rather than returned, so it never stops the rest of hygiene.
+fn compact_markdown_memory(config: &MemoryConfig, workspace_dir: &Path) -> (u64, u64) {
+    if classify_memory_backend(&config.backend) != MemoryBackendKind::Markdown {
+        return (0, 0);
+    }
+    match MarkdownMemory::new(workspace_dir).compact_files() {
+        Ok(stats) => (u64::try_from(stats.entries_compacted).unwrap_or(0), 0),
+        Err(err) => {
+            tracing::warn!("markdown memory compaction failed: {err:#}");
+            (0, 1)
+        }
+    }
+}
+
 fn memory_date_from_filename(filename: &str) -> Option<NaiveDate> {
     let stem = filename.strip_suffix(".md")?;
     let date_part = stem.split('_').next().unwrap_or(stem);
@@ -378,7 +408,7 @@ fn split_name(filename: &str) -> (&str, &str) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::memory::{Memory, MemoryCategory, SqliteMemory};
+    use crate::memory::{MarkdownMemory, Memory, MemoryCategory, SqliteMemory};
     use tempfile::TempDir;
 
     fn default_cfg() -> MemoryConfig {
@@ -536,4 +566,56 @@ mod tests {
             "core memory should remain"
         );
     }
+
+    #[tokio::test]
+    async fn compacts_superseded_markdown_entries_for_markdown_backend() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+        let mem = MarkdownMemory::new(workspace);
+        mem.store("lang", "Python", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("lang", "Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let sqlite = TempDir::new().unwrap();
+        fs::copy(workspace.join("MEMORY.md"), sqlite.path().join("MEMORY.md")).unwrap();
+        run_if_due(&default_cfg(), sqlite.path()).unwrap();
+        let untouched = fs::read_to_string(sqlite.path().join("MEMORY.md")).unwrap();
+        assert!(
+            untouched.contains("Python"),
+            "only the markdown backend is compacted"
+        );
+
+        let mut cfg = default_cfg();
+        cfg.backend = "markdown".into();
+        run_if_due(&cfg, workspace).unwrap();
+
+        let core = fs::read_to_string(workspace.join("MEMORY.md")).unwrap();
+        assert!(
+            !core.contains("Python"),
+            "superseded entry should be dropped"
+        );
+        assert!(core.contains("- **lang**: Rust"));
+        assert_eq!(mem.count().await.unwrap(), 1);
+    }
+
+    #[test]
+    fn markdown_compaction_failure_is_counted_without_aborting_hygiene() {
+        let tmp = TempDir::new().unwrap();
+        let workspace = tmp.path();
+        // A directory where a daily file should be makes compaction fail.
+        fs::create_dir_all(workspace.join("memory").join("broken.md")).unwrap();
+        let mut cfg = default_cfg();
+        cfg.backend = "markdown".into();
+
+        run_if_due(&cfg, workspace).unwrap();
+
+        let state: HygieneState =
+            serde_json::from_str(&fs::read_to_string(state_path(workspace)).unwrap()).unwrap();
+        assert!(state.last_run_at.is_some(), "hygiene should finish its run");
+        assert_eq!(state.last_report.failed_markdown_compactions, 1);
+        assert_eq!(state.last_report.compacted_markdown_entries, 0);
+    }
 }
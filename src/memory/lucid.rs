@@ -334,7 +334,10 @@ impl Memory for LucidMemory {
         session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
         let local_results = self.local.recall(query, limit, session_id).await?;
+        // Empty queries follow the local store's semantics; Lucid has no
+        // notion of recency to add.
         if limit == 0
+            || query.trim().is_empty()
             || local_results.len() >= limit
             || local_results.len() >= self.local_hit_threshold
         {
@@ -1,22 +1,72 @@
-use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use super::traits::{CompactionStats, Memory, MemoryCategory, MemoryEntry};
+use anyhow::Context;
 use async_trait::async_trait;
 use chrono::Local;
+use std::collections::HashMap;
+use std::io::Write;
 use std::path::{Path, PathBuf};
-use tokio::fs;
+use std::sync::{Arc, Mutex, OnceLock, PoisonError, RwLock};
+
+/// Summary of one category's on-disk footprint, as reported by
+/// [`MarkdownMemory::stats`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MarkdownCategoryStats {
+    pub category: MemoryCategory,
+    pub files: usize,
+    pub entries: usize,
+    /// Size of the files on disk, including any bytes that are not valid UTF-8.
+    pub bytes: u64,
+}
 
 /// Markdown-based memory — plain files as source of truth
 ///
 /// Layout:
 ///   workspace/MEMORY.md          — curated long-term memory (core)
 ///   workspace/memory/YYYY-MM-DD.md — daily logs (append-only)
+///
+/// Writes replace a file through a uniquely named temp file and rename, so
+/// readers see either the old or the new contents of a file, never a partial
+/// one. Files only grow until [`Self::compact_files`] drops superseded entries.
 pub struct MarkdownMemory {
     workspace_dir: PathBuf,
+    /// Shared by every instance on this workspace: appends hold it
+    /// exclusively, reads shared.
+    lock: Arc<RwLock<()>>,
+}
+
+/// Lock for `workspace_dir`, shared process-wide so separate `MarkdownMemory`
+/// instances on one workspace don't lose each other's appends.
+///
+/// Keyed on the absolute path with `.` and `..` resolved lexically. The
+/// filesystem is not consulted, so the key is the same before and after the
+/// workspace is created; paths reaching the workspace through different
+/// symlinks get separate locks.
+fn workspace_lock(workspace_dir: &Path) -> Arc<RwLock<()>> {
+    static LOCKS: OnceLock<Mutex<HashMap<PathBuf, Arc<RwLock<()>>>>> = OnceLock::new();
+    let absolute =
+        std::path::absolute(workspace_dir).unwrap_or_else(|_| workspace_dir.to_path_buf());
+    let mut key = PathBuf::new();
+    for component in absolute.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                key.pop();
+            }
+            other => key.push(other),
+        }
+    }
+    let mut locks = LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    Arc::clone(locks.entry(key).or_default())
 }
 
 impl MarkdownMemory {
     pub fn new(workspace_dir: &Path) -> Self {
         Self {
             workspace_dir: workspace_dir.to_path_buf(),
+            lock: workspace_lock(workspace_dir),
         }
     }
 
@@ -33,36 +83,140 @@ impl MarkdownMemory {
         self.memory_dir().join(format!("{date}.md"))
     }
 
-    async fn ensure_dirs(&self) -> anyhow::Result<()> {
-        fs::create_dir_all(self.memory_dir()).await?;
+    /// Replace `path` with `contents` via a uniquely named sibling temp file
+    /// and rename.
+    fn write_atomic(path: &Path, contents: &[u8]) -> anyhow::Result<()> {
+        let dir = path.parent().unwrap_or_else(|| Path::new("."));
+        let mut tmp = tempfile::NamedTempFile::new_in(dir)?;
+        tmp.write_all(contents)?;
+        tmp.persist(path)?;
         Ok(())
     }
 
     async fn append_to_file(&self, path: &Path, content: &str) -> anyhow::Result<()> {
-        self.ensure_dirs().await?;
-
-        let existing = if path.exists() {
-            fs::read_to_string(path).await.unwrap_or_default()
+        let header = if path == self.core_path() {
+            "# Long-Term Memory\n\n".to_string()
         } else {
-            String::new()
+            let date = Local::now().format("%Y-%m-%d").to_string();
+            format!("# Daily Log — {date}\n\n")
+        };
+        let lock = Arc::clone(&self.lock);
+        let memory_dir = self.memory_dir();
+        let path = path.to_path_buf();
+        let content = content.to_string();
+        tokio::task::spawn_blocking(move || {
+            let _write = lock.write().unwrap_or_else(PoisonError::into_inner);
+            std::fs::create_dir_all(&memory_dir)?;
+            Self::append_locked(&path, &header, &content)
+        })
+        .await
+        .context("markdown memory write task failed")?
+    }
+
+    /// Read-modify-write append of `content`; the caller holds the write lock.
+    fn append_locked(path: &Path, header: &str, content: &str) -> anyhow::Result<()> {
+        let existing = match std::fs::read(path) {
+            Ok(bytes) => {
+                let (text, corrupt) = Self::decode_lossy(&bytes);
+                // Rewrite cleanly below, but keep the dropped bytes for inspection.
+                Self::quarantine_corrupt(path, &corrupt)?;
+                text
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(err) => return Err(err.into()),
         };
 
         let updated = if existing.is_empty() {
-            let header = if path == self.core_path() {
-                "# Long-Term Memory\n\n"
-            } else {
-                let date = Local::now().format("%Y-%m-%d").to_string();
-                &format!("# Daily Log — {date}\n\n")
-            };
             format!("{header}{content}\n")
         } else {
             format!("{existing}\n{content}\n")
         };
 
-        fs::write(path, updated).await?;
+        Self::write_atomic(path, updated.as_bytes())
+    }
+
+    /// Append `corrupt` bytes dropped from `path` to its `.md.corrupt`
+    /// sidecar. Does nothing when there are none.
+    fn quarantine_corrupt(path: &Path, corrupt: &[u8]) -> anyhow::Result<()> {
+        if corrupt.is_empty() {
+            return Ok(());
+        }
+        let quarantine = path.with_extension("md.corrupt");
+        let mut previous = std::fs::read(&quarantine).unwrap_or_default();
+        previous.extend_from_slice(corrupt);
+        std::fs::write(&quarantine, previous)?;
+        tracing::warn!(
+            path = %path.display(),
+            quarantine = %quarantine.display(),
+            "moved corrupt markdown memory records aside"
+        );
         Ok(())
     }
 
+    /// Key of a `- **key**: content` line written by `store`.
+    fn entry_key(line: &str) -> Option<&str> {
+        let rest = line.trim().strip_prefix("- **")?;
+        rest.split_once("**: ").map(|(key, _)| key)
+    }
+
+    /// `content` with every keyed entry except the last one per key removed.
+    /// A dropped entry that stands in its own block also takes the blank
+    /// separator line `store` wrote before it; every other line, hand-written
+    /// text included, is kept byte-for-byte. Returns the new text and how many
+    /// entries were dropped.
+    fn compact_text(content: &str) -> (String, usize) {
+        let lines: Vec<&str> = content.split_inclusive('\n').collect();
+        let mut latest: HashMap<&str, usize> = HashMap::new();
+        for (i, line) in lines.iter().enumerate() {
+            if let Some(key) = Self::entry_key(line) {
+                latest.insert(key, i);
+            }
+        }
+
+        let is_blank = |line: &&str| line.trim().is_empty();
+        let mut kept: Vec<&str> = Vec::with_capacity(lines.len());
+        let mut dropped = 0;
+        for (i, line) in lines.iter().enumerate() {
+            if Self::entry_key(line).is_some_and(|key| latest[key] != i) {
+                dropped += 1;
+                let own_block = lines.get(i + 1).is_none_or(is_blank);
+                if own_block && kept.last().is_some_and(is_blank) {
+                    kept.pop();
+                }
+                continue;
+            }
+            kept.push(line);
+        }
+        (kept.concat(), dropped)
+    }
+
+    /// Rewrite every storage file so each key keeps only its latest entry and
+    /// corrupt records move to the `.md.corrupt` sidecar; everything else
+    /// stays as written. Files are only rewritten when they change, each
+    /// through [`Self::write_atomic`], so a crash leaves every file either
+    /// fully compacted or untouched.
+    ///
+    /// `forget` never removes entries, so there are no tombstones; repeated
+    /// `store`s of a key are the only superseded records. Synchronous so
+    /// memory hygiene can run it without a runtime.
+    pub fn compact_files(&self) -> anyhow::Result<CompactionStats> {
+        let _write = self.lock.write().unwrap_or_else(PoisonError::into_inner);
+        let mut stats = CompactionStats::default();
+        for (path, _) in self.list_storage_files()? {
+            let bytes = std::fs::read(&path)?;
+            let (text, corrupt) = Self::decode_lossy(&bytes);
+            let (compacted, dropped) = Self::compact_text(&text);
+            if compacted.as_bytes() == bytes.as_slice() {
+                continue;
+            }
+            Self::quarantine_corrupt(&path, &corrupt)?;
+            Self::write_atomic(&path, compacted.as_bytes())?;
+            stats.entries_compacted += dropped;
+            stats.bytes_reclaimed += (bytes.len() as u64).saturating_sub(compacted.len() as u64);
+        }
+        Ok(stats)
+    }
+
     fn parse_entries_from_file(
         path: &Path,
         content: &str,
@@ -96,39 +250,145 @@ impl MarkdownMemory {
             .collect()
     }
 
+    /// Split a storage file into its readable text and the raw bytes of any
+    /// lines that are not valid UTF-8 (e.g. a write truncated mid-character).
+    fn decode_lossy(bytes: &[u8]) -> (String, Vec<u8>) {
+        if let Ok(text) = std::str::from_utf8(bytes) {
+            return (text.to_string(), Vec::new());
+        }
+        let mut good = Vec::new();
+        let mut corrupt = Vec::new();
+        for line in bytes.split(|b| *b == b'\n') {
+            match std::str::from_utf8(line) {
+                Ok(text) => good.push(text),
+                Err(_) => {
+                    corrupt.extend_from_slice(line);
+                    corrupt.push(b'\n');
+                }
+            }
+        }
+        (good.join("\n"), corrupt)
+    }
+
+    /// Read a storage file, skipping corrupt records with a warning so the
+    /// rest of the file stays readable.
+    fn read_storage_file(path: &Path) -> anyhow::Result<String> {
+        let bytes = std::fs::read(path)?;
+        let (text, corrupt) = Self::decode_lossy(&bytes);
+        if !corrupt.is_empty() {
+            tracing::warn!(
+                path = %path.display(),
+                skipped_bytes = corrupt.len(),
+                "skipping corrupt records in markdown memory file"
+            );
+        }
+        Ok(text)
+    }
+
+    /// Position of an entry within its file, from the `<file>:<index>` key.
+    fn line_index(entry: &MemoryEntry) -> usize {
+        entry
+            .key
+            .rsplit(':')
+            .next()
+            .and_then(|i| i.parse().ok())
+            .unwrap_or(0)
+    }
+
+    /// Every entry, newest first. All files are read under the workspace
+    /// lock, so a concurrent store is either fully visible or not at all.
     async fn read_all_entries(&self) -> anyhow::Result<Vec<MemoryEntry>> {
-        let mut entries = Vec::new();
+        let reader = Self {
+            workspace_dir: self.workspace_dir.clone(),
+            lock: Arc::clone(&self.lock),
+        };
+        tokio::task::spawn_blocking(move || {
+            let _read = reader.lock.read().unwrap_or_else(PoisonError::into_inner);
+            let mut entries = Vec::new();
+            for (path, category) in reader.list_storage_files()? {
+                let content = Self::read_storage_file(&path)?;
+                entries.extend(Self::parse_entries_from_file(&path, &content, &category));
+            }
+            entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            Ok(entries)
+        })
+        .await
+        .context("markdown memory read task failed")?
+    }
+
+    /// List the files backing this memory, paired with the category their
+    /// entries are parsed as. `MEMORY.md` comes first, then daily logs in
+    /// date order.
+    ///
+    /// Synchronous so tooling (backups, debugging) can inspect the store
+    /// without going through the async `Memory` trait.
+    pub fn storage_files(&self) -> anyhow::Result<Vec<(PathBuf, MemoryCategory)>> {
+        let _read = self.lock.read().unwrap_or_else(PoisonError::into_inner);
+        self.list_storage_files()
+    }
+
+    fn list_storage_files(&self) -> anyhow::Result<Vec<(PathBuf, MemoryCategory)>> {
+        let mut files = Vec::new();
 
-        // Read MEMORY.md (core)
         let core_path = self.core_path();
-        if core_path.exists() {
-            let content = fs::read_to_string(&core_path).await?;
-            entries.extend(Self::parse_entries_from_file(
-                &core_path,
-                &content,
-                &MemoryCategory::Core,
-            ));
+        if core_path.is_file() {
+            files.push((core_path, MemoryCategory::Core));
         }
 
-        // Read daily logs
         let mem_dir = self.memory_dir();
-        if mem_dir.exists() {
-            let mut dir = fs::read_dir(&mem_dir).await?;
-            while let Some(entry) = dir.next_entry().await? {
-                let path = entry.path();
-                if path.extension().and_then(|e| e.to_str()) == Some("md") {
-                    let content = fs::read_to_string(&path).await?;
-                    entries.extend(Self::parse_entries_from_file(
-                        &path,
-                        &content,
-                        &MemoryCategory::Daily,
-                    ));
+        if mem_dir.is_dir() {
+            let mut daily: Vec<PathBuf> = std::fs::read_dir(&mem_dir)?
+                .filter_map(|entry| entry.ok().map(|e| e.path()))
+                .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("md"))
+                .collect();
+            daily.sort();
+            files.extend(daily.into_iter().map(|path| (path, MemoryCategory::Daily)));
+        }
+
+        Ok(files)
+    }
+
+    /// Return the raw on-disk contents of every storage file, in the same
+    /// order as [`Self::storage_files`]. Invalid UTF-8 is shown as U+FFFD
+    /// rather than failing the read. All files are read under the workspace
+    /// lock, so a concurrent store is either fully visible or not at all.
+    pub fn read_raw(&self) -> anyhow::Result<Vec<(PathBuf, String)>> {
+        let _read = self.lock.read().unwrap_or_else(PoisonError::into_inner);
+        self.list_storage_files()?
+            .into_iter()
+            .map(|(path, _)| {
+                let content = String::from_utf8_lossy(&std::fs::read(&path)?).into_owned();
+                Ok((path, content))
+            })
+            .collect()
+    }
+
+    /// Report file count, entry count, and total on-disk bytes per category.
+    /// Categories with no files on disk are omitted. Like [`Self::read_raw`],
+    /// the report is a snapshot taken under the workspace lock.
+    pub fn stats(&self) -> anyhow::Result<Vec<MarkdownCategoryStats>> {
+        let _read = self.lock.read().unwrap_or_else(PoisonError::into_inner);
+        let mut stats: Vec<MarkdownCategoryStats> = Vec::new();
+        for (path, category) in self.list_storage_files()? {
+            let raw = std::fs::read(&path)?;
+            let (content, _) = Self::decode_lossy(&raw);
+            let entries = Self::parse_entries_from_file(&path, &content, &category).len();
+            let bytes = raw.len() as u64;
+            match stats.iter_mut().find(|s| s.category == category) {
+                Some(existing) => {
+                    existing.files += 1;
+                    existing.entries += entries;
+                    existing.bytes += bytes;
                 }
+                None => stats.push(MarkdownCategoryStats {
+                    category,
+                    files: 1,
+                    entries,
+                    bytes,
+                }),
             }
         }
-
-        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
-        Ok(entries)
+        Ok(stats)
     }
 }
 
@@ -153,13 +413,30 @@ impl Memory for MarkdownMemory {
         self.append_to_file(&path, &entry).await
     }
 
+    /// An empty (or whitespace-only) query returns the most recent entries
+    /// instead of nothing: daily logs newest date first, then `MEMORY.md`
+    /// (which has no date), newest line first within each file, up to `limit`.
     async fn recall(
         &self,
         query: &str,
         limit: usize,
         _session_id: Option<&str>,
     ) -> anyhow::Result<Vec<MemoryEntry>> {
-        let all = self.read_all_entries().await?;
+        let mut all = self.read_all_entries().await?;
+        if query.trim().is_empty() {
+            // Core entries carry the file stem as their timestamp, which would
+            // sort above every date; rank them after the dated daily logs.
+            all.sort_by(|a, b| {
+                let a_core = a.category == MemoryCategory::Core;
+                let b_core = b.category == MemoryCategory::Core;
+                a_core
+                    .cmp(&b_core)
+                    .then_with(|| b.timestamp.cmp(&a.timestamp))
+                    .then_with(|| Self::line_index(b).cmp(&Self::line_index(a)))
+            });
+            all.truncate(limit);
+            return Ok(all);
+        }
         let query_lower = query.to_lowercase();
         let keywords: Vec<&str> = query_lower.split_whitespace().collect();
 
@@ -224,12 +501,23 @@ impl Memory for MarkdownMemory {
     async fn health_check(&self) -> bool {
         self.workspace_dir.exists()
     }
+
+    async fn compact(&self) -> anyhow::Result<CompactionStats> {
+        let compactor = Self {
+            workspace_dir: self.workspace_dir.clone(),
+            lock: Arc::clone(&self.lock),
+        };
+        tokio::task::spawn_blocking(move || compactor.compact_files())
+            .await
+            .context("markdown memory compaction task failed")?
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
+    use tokio::fs;
 
     fn temp_workspace() -> (TempDir, MarkdownMemory) {
         let tmp = TempDir::new().unwrap();
@@ -290,6 +578,48 @@ mod tests {
             .all(|r| r.content.to_lowercase().contains("rust")));
     }
 
+    #[tokio::test]
+    async fn markdown_empty_query_recall_returns_most_recent() {
+        let (_tmp, mem) = temp_workspace();
+        for (key, content) in [("a", "first"), ("b", "second"), ("c", "third")] {
+            mem.store(key, content, MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+
+        let results = mem.recall("", 2, None).await.unwrap();
+        let contents: Vec<&str> = results.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["**c**: third", "**b**: second"]);
+
+        let whitespace = mem.recall("   ", 10, None).await.unwrap();
+        assert_eq!(whitespace.len(), 3);
+        assert_eq!(whitespace[2].content, "**a**: first");
+    }
+
+    #[tokio::test]
+    async fn markdown_empty_query_recall_ranks_daily_logs_by_date_before_core() {
+        let (_tmp, mem) = temp_workspace();
+        mem.store("core", "curated", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("today", "fresh", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        fs::write(
+            mem.memory_dir().join("2020-01-01.md"),
+            "# Daily Log — 2020-01-01\n\n- **old**: stale\n",
+        )
+        .await
+        .unwrap();
+
+        let results = mem.recall("", 10, None).await.unwrap();
+        let contents: Vec<&str> = results.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec!["**today**: fresh", "**old**: stale", "**core**: curated"]
+        );
+    }
+
     #[tokio::test]
     async fn markdown_recall_no_match() {
         let (_tmp, mem) = temp_workspace();
@@ -347,6 +677,223 @@ mod tests {
         assert!(results.is_empty());
     }
 
+    #[tokio::test]
+    async fn markdown_introspection_reflects_stored_entries() {
+        let (_tmp, mem) = temp_workspace();
+        assert!(mem.storage_files().unwrap().is_empty());
+        assert!(mem.stats().unwrap().is_empty());
+
+        mem.store("a", "core one", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("b", "core two", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        mem.store("c", "daily one", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+
+        let files = mem.storage_files().unwrap();
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], (mem.core_path(), MemoryCategory::Core));
+        assert_eq!(files[1], (mem.daily_path(), MemoryCategory::Daily));
+
+        let raw = mem.read_raw().unwrap();
+        assert!(raw[0].1.contains("- **b**: core two"));
+        assert!(raw[1].1.contains("- **c**: daily one"));
+
+        let stats = mem.stats().unwrap();
+        let core = stats
+            .iter()
+            .find(|s| s.category == MemoryCategory::Core)
+            .unwrap();
+        assert_eq!(core.files, 1);
+        assert_eq!(core.entries, 2);
+        assert_eq!(core.bytes, raw[0].1.len() as u64);
+        let daily = stats
+            .iter()
+            .find(|s| s.category == MemoryCategory::Daily)
+            .unwrap();
+        assert_eq!(daily.entries, 1);
+        assert_eq!(core.entries + daily.entries, mem.count().await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn markdown_skips_corrupt_records_and_rewrites_cleanly() {
+        let (_tmp, mem) = temp_workspace();
+        let mut raw = b"# Long-Term Memory\n\n- **a**: survives\n".to_vec();
+        raw.extend_from_slice(b"- **b**: trunc\xe2\x82\n");
+        raw.extend_from_slice(b"- **c**: also survives\n");
+        fs::write(mem.core_path(), &raw).await.unwrap();
+        assert_eq!(mem.stats().unwrap()[0].bytes, raw.len() as u64);
+
+        let entries = mem.list(Some(&MemoryCategory::Core), None).await.unwrap();
+        let contents: Vec<&str> = entries.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["**a**: survives", "**c**: also survives"]);
+        assert_eq!(mem.recall("survives", 10, None).await.unwrap().len(), 2);
+
+        mem.store("d", "after corruption", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let rewritten = fs::read_to_string(mem.core_path()).await.unwrap();
+        assert!(rewritten.contains("**a**: survives"));
+        assert!(rewritten.contains("**c**: also survives"));
+        assert!(rewritten.contains("**d**: after corruption"));
+        assert!(!rewritten.contains("trunc"));
+
+        let quarantined = fs::read(mem.core_path().with_extension("md.corrupt"))
+            .await
+            .unwrap();
+        assert_eq!(quarantined, b"- **b**: trunc\xe2\x82\n");
+        assert_eq!(mem.count().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn markdown_compact_keeps_latest_version_of_each_key() {
+        let (_tmp, mem) = temp_workspace();
+        for (key, content) in [("lang", "Python"), ("editor", "vim"), ("lang", "Rust")] {
+            mem.store(key, content, MemoryCategory::Core, None)
+                .await
+                .unwrap();
+        }
+        mem.store("lang", "daily note", MemoryCategory::Daily, None)
+            .await
+            .unwrap();
+        let before = fs::metadata(mem.core_path()).await.unwrap().len();
+
+        let stats = mem.compact().await.unwrap();
+        assert_eq!(stats.entries_compacted, 1);
+        let after = fs::metadata(mem.core_path()).await.unwrap().len();
+        assert_eq!(stats.bytes_reclaimed, before - after);
+
+        let core = fs::read_to_string(mem.core_path()).await.unwrap();
+        assert_eq!(
+            core,
+            "# Long-Term Memory\n\n- **editor**: vim\n\n- **lang**: Rust\n"
+        );
+        assert!(mem.recall("Python", 10, None).await.unwrap().is_empty());
+        assert_eq!(mem.recall("daily note", 10, None).await.unwrap().len(), 1);
+        assert_eq!(mem.count().await.unwrap(), 3);
+
+        assert_eq!(mem.compact().await.unwrap(), CompactionStats::default());
+    }
+
+    #[tokio::test]
+    async fn markdown_compact_keeps_hand_written_content_byte_for_byte() {
+        let (_tmp, mem) = temp_workspace();
+        let hand_written = "# Long-Term Memory\r\n\n\
+Notes I keep by hand.\n  Indented line, trailing spaces   \n\n\n\
+* a bullet in another style\n- plain dash bullet\n\n\
+- **lang**: Python\n\
+Prose right under an old entry.\n\n\
+- **lang**: Rust\n\n\
+## Footer without a final newline";
+        fs::write(mem.core_path(), hand_written).await.unwrap();
+
+        let stats = mem.compact_files().unwrap();
+        assert_eq!(stats.entries_compacted, 1);
+        let core = fs::read_to_string(mem.core_path()).await.unwrap();
+        assert_eq!(
+            core,
+            hand_written.replacen("- **lang**: Python\n", "", 1),
+            "only the superseded entry line is removed"
+        );
+    }
+
+    #[tokio::test]
+    async fn markdown_compact_moves_corrupt_records_aside() {
+        let (_tmp, mem) = temp_workspace();
+        let mut raw = b"# Long-Term Memory\n\n- **a**: kept\n\n\n".to_vec();
+        raw.extend_from_slice(b"- **b**: trunc\xe2\x82\n");
+        fs::write(mem.core_path(), &raw).await.unwrap();
+
+        let stats = mem.compact_files().unwrap();
+        assert_eq!(stats.entries_compacted, 0);
+        assert!(stats.bytes_reclaimed > 0);
+        let core = fs::read_to_string(mem.core_path()).await.unwrap();
+        assert_eq!(core, "# Long-Term Memory\n\n- **a**: kept\n\n\n");
+        let quarantined = fs::read(mem.core_path().with_extension("md.corrupt"))
+            .await
+            .unwrap();
+        assert_eq!(quarantined, b"- **b**: trunc\xe2\x82\n");
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn markdown_concurrent_stores_keep_every_line() {
+        let (_tmp, mem) = temp_workspace();
+        let mem = std::sync::Arc::new(mem);
+        let writers: Vec<_> = (0..32)
+            .map(|i| {
+                let mem = std::sync::Arc::clone(&mem);
+                tokio::spawn(async move {
+                    mem.store(&format!("k{i}"), "value", MemoryCategory::Core, None)
+                        .await
+                        .unwrap();
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.await.unwrap();
+        }
+
+        assert_eq!(mem.count().await.unwrap(), 32);
+        assert_eq!(mem.stats().unwrap()[0].entries, 32);
+        let leftovers: Vec<_> = std::fs::read_dir(mem.workspace_dir.clone())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name())
+            .filter(|name| name != "MEMORY.md" && name != "memory")
+            .collect();
+        assert!(
+            leftovers.is_empty(),
+            "temp files left behind: {leftovers:?}"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn markdown_instances_on_one_workspace_share_the_lock() {
+        let tmp = TempDir::new().unwrap();
+        let writer = MarkdownMemory::new(tmp.path());
+        // Spelled differently, and before `memory/` exists.
+        let reader = std::sync::Arc::new(MarkdownMemory::new(
+            &tmp.path().join("memory").join("..").join("."),
+        ));
+        assert!(Arc::ptr_eq(&writer.lock, &reader.lock));
+
+        // While another instance holds the lock for an append, reads wait
+        // for it instead of seeing a partial store.
+        let (locked_tx, locked_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let holder = std::thread::spawn({
+            let lock = Arc::clone(&writer.lock);
+            move || {
+                let _held = lock.write().unwrap();
+                locked_tx.send(()).unwrap();
+                release_rx.recv().unwrap();
+            }
+        });
+        locked_rx.recv().unwrap();
+        let read = tokio::spawn({
+            let reader = std::sync::Arc::clone(&reader);
+            async move { reader.count().await }
+        });
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        assert!(!read.is_finished(), "read must wait for the write lock");
+        release_tx.send(()).unwrap();
+        holder.join().unwrap();
+        let count = tokio::time::timeout(std::time::Duration::from_secs(5), read)
+            .await
+            .expect("read finishes once the lock is released")
+            .unwrap()
+            .unwrap();
+        assert_eq!(count, 0);
+
+        writer
+            .store("k", "value", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert_eq!(reader.count().await.unwrap(), 1);
+    }
+
     #[tokio::test]
     async fn markdown_empty_count() {
         let (_tmp, mem) = temp_workspace();
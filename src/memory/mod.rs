@@ -9,12 +9,14 @@ pub mod hygiene;
 pub mod lucid;
 pub mod markdown;
 pub mod none;
+pub mod plugin;
 #[cfg(feature = "memory-postgres")]
 pub mod postgres;
 pub mod qdrant;
 pub mod response_cache;
 pub mod snapshot;
 pub mod sqlite;
+pub mod tiered;
 pub mod traits;
 pub mod vector;
 
@@ -28,16 +30,18 @@ pub use hybrid::SqliteQdrantHybridMemory;
 pub use lucid::LucidMemory;
 pub use markdown::MarkdownMemory;
 pub use none::NoneMemory;
+pub use plugin::PluginMemory;
 #[cfg(feature = "memory-postgres")]
 pub use postgres::PostgresMemory;
 pub use qdrant::QdrantMemory;
 pub use response_cache::ResponseCache;
 pub use sqlite::SqliteMemory;
+pub use tiered::TieredMemory;
 pub use traits::Memory;
 #[allow(unused_imports)]
-pub use traits::{MemoryCategory, MemoryEntry};
+pub use traits::{CompactionStats, MemoryCategory, MemoryEntry};
 
-use crate::config::{EmbeddingRouteConfig, MemoryConfig, StorageProviderConfig};
+use crate::config::{EmbeddingRouteConfig, MemoryConfig, PluginsConfig, StorageProviderConfig};
 use anyhow::Context;
 use std::path::Path;
 use std::sync::Arc;
@@ -180,6 +184,42 @@ fn resolve_embedding_config(
     }
 }
 
+/// Reject tier lists that cannot behave as a cache.
+///
+/// `plugin:<id>` tiers are checked against the plugin registry when built.
+/// `none` and unknown backends (which fall back to markdown) are refused.
+/// SQLite-based backends all share the workspace `brain.db`, so at most one
+/// may appear.
+fn validate_tier_names(tier_names: &[&str]) -> anyhow::Result<()> {
+    let mut sqlite_tier: Option<&str> = None;
+    for tier_name in tier_names {
+        if tier_name.starts_with("tiered:") {
+            anyhow::bail!("memory backend 'tiered:' cannot be nested");
+        }
+        if let Some(plugin_id) = tier_name.strip_prefix("plugin:") {
+            if plugin_id.trim().is_empty() {
+                anyhow::bail!("memory tier 'plugin:' requires a plugin id");
+            }
+            continue;
+        }
+        if matches!(
+            classify_memory_backend(tier_name),
+            MemoryBackendKind::None | MemoryBackendKind::Unknown
+        ) {
+            anyhow::bail!("memory backend '{tier_name}' cannot be used as a tier of 'tiered:'");
+        }
+        if memory_backend_profile(tier_name).sqlite_based {
+            if let Some(previous) = sqlite_tier {
+                anyhow::bail!(
+                    "memory tiers '{previous}' and '{tier_name}' would share the same SQLite database"
+                );
+            }
+            sqlite_tier = Some(tier_name);
+        }
+    }
+    Ok(())
+}
+
 /// Factory: create the right memory backend from config
 pub fn create_memory(
     config: &MemoryConfig,
@@ -199,6 +239,32 @@ pub fn create_memory_with_storage(
     create_memory_with_storage_and_routes(config, &[], storage_provider, workspace_dir, api_key)
 }
 
+/// Factory used at startup: create memory so `plugin:<id>` tiers such as
+/// `tiered:plugin:redis,markdown` resolve. When the backend names a plugin
+/// tier, the plugin registry is loaded from `plugins` first (a no-op if it is
+/// already current) and a load failure fails memory creation.
+pub fn create_memory_with_plugins(
+    config: &MemoryConfig,
+    plugins: &PluginsConfig,
+    embedding_routes: &[EmbeddingRouteConfig],
+    storage_provider: Option<&StorageProviderConfig>,
+    workspace_dir: &Path,
+    api_key: Option<&str>,
+) -> anyhow::Result<Box<dyn Memory>> {
+    let backend_name = effective_memory_backend_name(&config.backend, storage_provider);
+    if backend_name.contains("plugin:") {
+        crate::plugins::runtime::initialize_from_config(plugins)
+            .context("failed to load plugins for plugin memory tiers")?;
+    }
+    create_memory_with_storage_and_routes(
+        config,
+        embedding_routes,
+        storage_provider,
+        workspace_dir,
+        api_key,
+    )
+}
+
 /// Factory: create memory with optional storage-provider override and embedding routes.
 pub fn create_memory_with_storage_and_routes(
     config: &MemoryConfig,
@@ -208,6 +274,53 @@ pub fn create_memory_with_storage_and_routes(
     api_key: Option<&str>,
 ) -> anyhow::Result<Box<dyn Memory>> {
     let backend_name = effective_memory_backend_name(&config.backend, storage_provider);
+
+    // `tiered:a,b` composes backends front to back; the last one is authoritative.
+    // A `plugin:<id>` tier is served by a registered memory plugin.
+    if let Some(tier_list) = backend_name.strip_prefix("tiered:") {
+        let tier_names: Vec<&str> = tier_list
+            .split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .collect();
+        if tier_names.is_empty() {
+            anyhow::bail!("memory backend 'tiered:' requires at least one tier");
+        }
+        validate_tier_names(&tier_names)?;
+        let mut tiers = Vec::with_capacity(tier_names.len());
+        let mut strict_tiers = Vec::new();
+        for tier_name in tier_names {
+            if let Some(plugin_id) = tier_name.strip_prefix("plugin:") {
+                let plugin = PluginMemory::new(plugin_id.trim())?;
+                if !plugin.read_fallback() {
+                    strict_tiers.push(tiers.len());
+                }
+                tiers.push(Box::new(plugin) as Box<dyn Memory>);
+                continue;
+            }
+            let tier_config = MemoryConfig {
+                backend: tier_name.to_string(),
+                ..config.clone()
+            };
+            // Keep provider connection settings (e.g. postgres db_url) for each tier.
+            let tier_storage = storage_provider.map(|storage| StorageProviderConfig {
+                provider: tier_name.to_string(),
+                ..storage.clone()
+            });
+            tiers.push(create_memory_with_storage_and_routes(
+                &tier_config,
+                embedding_routes,
+                tier_storage.as_ref(),
+                workspace_dir,
+                api_key,
+            )?);
+        }
+        let tiered = strict_tiers
+            .into_iter()
+            .fold(TieredMemory::new(tiers)?, TieredMemory::with_strict_reads);
+        return Ok(Box::new(tiered));
+    }
+
     let backend_kind = classify_memory_backend(&backend_name);
     let resolved_embedding = resolve_embedding_config(config, embedding_routes, api_key);
 
@@ -512,6 +625,63 @@ mod tests {
         assert_eq!(mem.name(), "sqlite_qdrant_hybrid");
     }
 
+    #[test]
+    fn factory_tiered_rejects_tiers_that_cannot_cache() {
+        let tmp = TempDir::new().unwrap();
+        for backend in [
+            "tiered:plugin:,markdown",
+            "tiered:none,sqlite",
+            "tiered:mystery,sqlite",
+            "tiered:sqlite,lucid",
+            "tiered:sqlite,tiered:sqlite",
+        ] {
+            let cfg = MemoryConfig {
+                backend: backend.into(),
+                ..MemoryConfig::default()
+            };
+            assert!(
+                create_memory(&cfg, tmp.path(), None).is_err(),
+                "{backend} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn factory_tiered_composes_keyed_backends() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = MemoryConfig {
+            backend: "tiered:sqlite, qdrant".into(),
+            qdrant: crate::config::QdrantConfig {
+                url: Some("http://localhost:6333".into()),
+                ..crate::config::QdrantConfig::default()
+            },
+            ..MemoryConfig::default()
+        };
+        let mem = create_memory(&cfg, tmp.path(), None).unwrap();
+        assert_eq!(mem.name(), "tiered");
+    }
+
+    #[test]
+    fn factory_tiered_accepts_markdown_tiers() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = MemoryConfig {
+            backend: "tiered:sqlite,markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let mem = create_memory(&cfg, tmp.path(), None).unwrap();
+        assert_eq!(mem.name(), "tiered");
+    }
+
+    #[test]
+    fn factory_tiered_rejects_empty_tier_list() {
+        let tmp = TempDir::new().unwrap();
+        let cfg = MemoryConfig {
+            backend: "tiered:".into(),
+            ..MemoryConfig::default()
+        };
+        assert!(create_memory(&cfg, tmp.path(), None).is_err());
+    }
+
     #[test]
     fn factory_none_uses_noop_memory() {
         let tmp = TempDir::new().unwrap();
@@ -0,0 +1,484 @@
+use super::traits::{Memory, MemoryCategory, MemoryEntry};
+use crate::plugins::runtime;
+use crate::plugins::PluginCapability;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+/// Host-side rewrite of content before [`PluginMemory`] stores it, such as
+/// truncation or a summary produced by a provided closure.
+pub type StoreTransform = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+/// Memory backend served by a WASM plugin declaring the `Memory` capability.
+///
+/// Each operation is one `zeroclaw_memory_call` with a JSON request such as
+/// `{"op": "get", "key": "..."}`; see `docs/plugins-runtime.md` for the reply
+/// each operation expects. Used as a `plugin:<id>` tier of `tiered:`.
+pub struct PluginMemory {
+    plugin_id: String,
+    store_transform: Option<StoreTransform>,
+    /// Also send the untransformed content as `original_content`.
+    keep_original: bool,
+    /// `[plugins.entries.<id>].read_fallback`: whether other tiers may answer
+    /// reads while this plugin fails.
+    read_fallback: bool,
+    /// `[plugins.entries.<id>].allowed_categories`; `None` allows all.
+    allowed_categories: Option<Vec<String>>,
+}
+
+impl PluginMemory {
+    /// Memory backed by plugin `plugin_id`, which must already be registered
+    /// and declare the `Memory` capability.
+    pub fn new(plugin_id: &str) -> Result<Self> {
+        let registry = runtime::current_registry();
+        let manifest = registry
+            .manifest(plugin_id)
+            .with_context(|| format!("memory plugin '{plugin_id}' is not registered"))?;
+        anyhow::ensure!(
+            manifest.capabilities.contains(&PluginCapability::Memory),
+            "plugin '{plugin_id}' does not declare the Memory capability"
+        );
+        let settings = registry.settings(plugin_id).cloned().unwrap_or_default();
+        Ok(Self {
+            plugin_id: plugin_id.to_string(),
+            store_transform: None,
+            keep_original: false,
+            read_fallback: settings.read_fallback.unwrap_or(true),
+            allowed_categories: settings.allowed_categories,
+        })
+    }
+
+    /// Whether a tiered backend may answer reads from other tiers while this
+    /// plugin fails. `false` makes its tier strict; see
+    /// [`super::TieredMemory::with_strict_reads`].
+    pub fn read_fallback(&self) -> bool {
+        self.read_fallback
+    }
+
+    /// Rewrite content with `transform` before it is stored. With
+    /// `keep_original`, a store whose content changed also sends the
+    /// untransformed text as `original_content`, for the plugin to keep as
+    /// metadata. Without a transform, content is stored as given.
+    pub fn with_store_transform(mut self, transform: StoreTransform, keep_original: bool) -> Self {
+        self.store_transform = Some(transform);
+        self.keep_original = keep_original;
+        self
+    }
+
+    fn ensure_category_allowed(&self, category: &MemoryCategory) -> Result<()> {
+        let Some(allowed) = &self.allowed_categories else {
+            return Ok(());
+        };
+        let category = category.to_string();
+        anyhow::ensure!(
+            allowed
+                .iter()
+                .any(|name| name.trim().eq_ignore_ascii_case(&category)),
+            "memory plugin '{}' may not store category '{category}' (allowed: {})",
+            self.plugin_id,
+            allowed.join(", ")
+        );
+        Ok(())
+    }
+
+    fn store_request(
+        &self,
+        key: &str,
+        content: &str,
+        category: &MemoryCategory,
+        session_id: Option<&str>,
+    ) -> Value {
+        let mut request = json!({
+            "op": "store",
+            "key": key,
+            "content": content,
+            "category": category,
+            "session_id": session_id,
+        });
+        if let Some(transform) = &self.store_transform {
+            let transformed = transform(content);
+            if self.keep_original && transformed != content {
+                request["original_content"] = json!(content);
+            }
+            request["content"] = json!(transformed);
+        }
+        request
+    }
+
+    async fn call(&self, request: Value) -> Result<Value> {
+        runtime::execute_plugin_memory(&self.plugin_id, &request).await
+    }
+
+    fn decode<T: serde::de::DeserializeOwned>(&self, op: &str, reply: Value) -> Result<T> {
+        serde_json::from_value(reply).with_context(|| {
+            format!(
+                "memory plugin '{}' returned an invalid '{op}' reply",
+                self.plugin_id
+            )
+        })
+    }
+}
+
+#[async_trait]
+impl Memory for PluginMemory {
+    fn name(&self) -> &str {
+        "plugin"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        self.ensure_category_allowed(&category)?;
+        self.call(self.store_request(key, content, &category, session_id))
+            .await?;
+        Ok(())
+    }
+
+    /// The query is passed through as-is. For an empty query the reply is
+    /// also sorted newest first and cut to `limit` here, for plugins that
+    /// don't implement recency themselves.
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let reply = self
+            .call(json!({
+                "op": "recall",
+                "query": query,
+                "limit": limit,
+                "session_id": session_id,
+            }))
+            .await?;
+        let mut entries: Vec<MemoryEntry> = self.decode("recall", reply)?;
+        if query.trim().is_empty() {
+            entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+            entries.truncate(limit);
+        }
+        Ok(entries)
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+        let reply = self.call(json!({ "op": "get", "key": key })).await?;
+        self.decode("get", reply)
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let reply = self
+            .call(json!({
+                "op": "list",
+                "category": category,
+                "session_id": session_id,
+            }))
+            .await?;
+        self.decode("list", reply)
+    }
+
+    async fn forget(&self, key: &str) -> Result<bool> {
+        let reply = self.call(json!({ "op": "forget", "key": key })).await?;
+        self.decode("forget", reply)
+    }
+
+    async fn count(&self) -> Result<usize> {
+        let reply = self.call(json!({ "op": "count" })).await?;
+        self.decode("count", reply)
+    }
+
+    async fn health_check(&self) -> bool {
+        self.count().await.is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{MemoryConfig, PluginsConfig};
+    use tempfile::TempDir;
+
+    /// Write memory plugin `id`, whose `zeroclaw_memory_call` always answers
+    /// `reply`, into its own directory and return a config that loads it.
+    fn memory_plugin(dir: &TempDir, id: &str, reply: &str) -> PluginsConfig {
+        let module = dir.path().join(format!("{id}.wasm"));
+        let wat_src = format!(
+            r#"
+(module
+  (memory (export "memory") 1)
+  (data (i32.const 64) "{escaped}")
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_memory_call") (param i32 i32) (result i64)
+    (i64.or (i64.shl (i64.const 64) (i64.const 32)) (i64.const {len}))))
+"#,
+            escaped = reply.replace('"', "\\\""),
+            len = reply.len(),
+        );
+        std::fs::write(
+            &module,
+            wat::parse_str(&wat_src).expect("valid wat fixture"),
+        )
+        .expect("write wasm fixture");
+        std::fs::write(
+            dir.path().join(format!("{id}.plugin.toml")),
+            format!(
+                r#"
+id = "{id}"
+version = "1.0.0"
+module_path = "{}"
+wit_packages = ["zeroclaw:memory@1.0.0"]
+capabilities = ["Memory"]
+"#,
+                module.display()
+            ),
+        )
+        .expect("write manifest");
+        PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        }
+    }
+
+    fn unregistered(plugin_id: &str) -> PluginMemory {
+        PluginMemory {
+            plugin_id: plugin_id.to_string(),
+            store_transform: None,
+            keep_original: false,
+            read_fallback: true,
+            allowed_categories: None,
+        }
+    }
+
+    #[test]
+    fn store_transform_rewrites_content_and_can_keep_the_original() {
+        let truncate: StoreTransform = Arc::new(|content| content.chars().take(8).collect());
+        let long = "a long note that should be shortened";
+
+        let plain = unregistered("kv").store_request("k", long, &MemoryCategory::Core, None);
+        assert_eq!(plain["content"], long);
+        assert!(plain.get("original_content").is_none());
+
+        let memory = unregistered("kv").with_store_transform(Arc::clone(&truncate), false);
+        let request = memory.store_request("k", long, &MemoryCategory::Core, None);
+        assert_eq!(request["content"], "a long n");
+        assert!(request.get("original_content").is_none());
+
+        let memory = unregistered("kv").with_store_transform(truncate, true);
+        let request = memory.store_request("k", long, &MemoryCategory::Core, None);
+        assert_eq!(request["content"], "a long n");
+        assert_eq!(request["original_content"], long);
+        let short = memory.store_request("k", "short", &MemoryCategory::Core, None);
+        assert!(
+            short.get("original_content").is_none(),
+            "unchanged content carries no copy"
+        );
+    }
+
+    #[tokio::test]
+    async fn plugin_memory_requires_a_registered_memory_plugin() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().unwrap();
+        runtime::initialize_from_config(&memory_plugin(&dir, "kv-registered", "null"))
+            .expect("initialize plugins");
+
+        assert!(PluginMemory::new("kv-registered").is_ok());
+        let err = PluginMemory::new("kv-absent")
+            .err()
+            .expect("unknown plugin");
+        assert!(err.to_string().contains("not registered"), "{err:#}");
+    }
+
+    #[tokio::test]
+    async fn plugin_memory_decodes_entries_and_surfaces_guest_errors() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().unwrap();
+        runtime::initialize_from_config(&memory_plugin(
+            &dir,
+            "kv-entries",
+            r#"[{"id":"1","key":"lang","content":"Rust","category":"core","timestamp":"2026-01-01T00:00:00Z","session_id":null,"score":null}]"#,
+        ))
+        .expect("initialize plugins");
+        let memory = PluginMemory::new("kv-entries").unwrap();
+        let recalled = memory.recall("Rust", 5, None).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert_eq!(recalled[0].key, "lang");
+        assert!(memory.get("lang").await.is_err(), "a list is not an entry");
+
+        let dir = TempDir::new().unwrap();
+        runtime::initialize_from_config(&memory_plugin(
+            &dir,
+            "kv-offline",
+            r#"{"error":"cache offline"}"#,
+        ))
+        .expect("initialize plugins");
+        let err = PluginMemory::new("kv-offline")
+            .unwrap()
+            .store("lang", "Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("cache offline"), "{err:#}");
+    }
+
+    #[tokio::test]
+    async fn success_false_without_an_error_fails_the_operation() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().unwrap();
+        runtime::initialize_from_config(&memory_plugin(&dir, "kv-silent", r#"{"success":false}"#))
+            .expect("initialize plugins");
+        let memory = PluginMemory::new("kv-silent").unwrap();
+
+        let err = memory
+            .store("lang", "Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("failure without detail"),
+            "{err:#}"
+        );
+        assert!(memory.forget("lang").await.is_err());
+        assert!(memory.count().await.is_err());
+    }
+
+    #[tokio::test]
+    async fn stores_outside_allowed_categories_are_refused() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().unwrap();
+        let mut plugins = memory_plugin(&dir, "kv-scratch", "null");
+        plugins.entries.insert(
+            "kv-scratch".into(),
+            crate::config::PluginEntryConfig {
+                allowed_categories: Some(vec!["conversation".into()]),
+                ..crate::config::PluginEntryConfig::default()
+            },
+        );
+        runtime::initialize_from_config(&plugins).expect("initialize plugins");
+        let memory = PluginMemory::new("kv-scratch").unwrap();
+
+        memory
+            .store("turn", "hello", MemoryCategory::Conversation, None)
+            .await
+            .expect("allowed category passes through");
+        let err = memory
+            .store("lang", "Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("may not store category 'core'"),
+            "{err:#}"
+        );
+    }
+
+    #[tokio::test]
+    async fn empty_query_recall_is_sorted_newest_first_host_side() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().unwrap();
+        let entry = |key: &str, timestamp: &str| {
+            format!(
+                r#"{{"id":"{key}","key":"{key}","content":"{key}","category":"core","timestamp":"{timestamp}","session_id":null,"score":null}}"#
+            )
+        };
+        let reply = format!(
+            "[{},{},{}]",
+            entry("old", "2026-01-01T00:00:00Z"),
+            entry("new", "2026-03-01T00:00:00Z"),
+            entry("mid", "2026-02-01T00:00:00Z"),
+        );
+        runtime::initialize_from_config(&memory_plugin(&dir, "kv-unsorted", &reply))
+            .expect("initialize plugins");
+        let memory = PluginMemory::new("kv-unsorted").unwrap();
+
+        let recent = memory.recall("  ", 2, None).await.unwrap();
+        let keys: Vec<&str> = recent.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["new", "mid"]);
+
+        let searched = memory.recall("any", 2, None).await.unwrap();
+        assert_eq!(
+            searched.len(),
+            3,
+            "non-empty queries keep the plugin's answer"
+        );
+    }
+
+    #[tokio::test]
+    async fn factory_builds_plugin_cache_over_markdown() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let plugins_dir = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        let plugins = memory_plugin(&plugins_dir, "kv-cache", r#"{"error":"cache offline"}"#);
+        let create = |backend: &str| {
+            let config = MemoryConfig {
+                backend: backend.into(),
+                ..MemoryConfig::default()
+            };
+            super::super::create_memory_with_plugins(
+                &config,
+                &plugins,
+                &[],
+                None,
+                workspace.path(),
+                None,
+            )
+        };
+
+        let memory = create("tiered:plugin:kv-cache,markdown").unwrap();
+        assert_eq!(memory.name(), "tiered");
+        // The cache tier is down; the authoritative markdown tier still serves.
+        memory
+            .store("lang", "Rust is the language", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        let recalled = memory.recall("Rust", 5, None).await.unwrap();
+        assert_eq!(recalled.len(), 1);
+        assert!(recalled[0].content.contains("Rust is the language"));
+
+        assert!(create("tiered:plugin:kv-missing,markdown").is_err());
+    }
+
+    #[tokio::test]
+    async fn factory_fails_reads_for_plugins_without_read_fallback() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let plugins_dir = TempDir::new().unwrap();
+        let workspace = TempDir::new().unwrap();
+        let mut plugins = memory_plugin(&plugins_dir, "kv-strict", r#"{"error":"cache offline"}"#);
+        plugins.entries.insert(
+            "kv-strict".into(),
+            crate::config::PluginEntryConfig {
+                read_fallback: Some(false),
+                ..crate::config::PluginEntryConfig::default()
+            },
+        );
+        let config = MemoryConfig {
+            backend: "tiered:plugin:kv-strict,markdown".into(),
+            ..MemoryConfig::default()
+        };
+        let memory = super::super::create_memory_with_plugins(
+            &config,
+            &plugins,
+            &[],
+            None,
+            workspace.path(),
+            None,
+        )
+        .unwrap();
+
+        // Writes still reach the authoritative tier...
+        memory
+            .store("lang", "Rust is the language", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        // ...but reads don't quietly come from it while the plugin is down.
+        let err = memory.recall("Rust", 5, None).await.unwrap_err();
+        assert!(err.to_string().contains("cache offline"), "{err:#}");
+        assert!(memory.get("lang").await.is_err());
+        assert!(memory.list(None, None).await.is_err());
+    }
+}
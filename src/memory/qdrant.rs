@@ -160,6 +160,13 @@ impl QdrantMemory {
         Ok(())
     }
 
+    /// Up to `limit` of `entries`, newest first by RFC 3339 timestamp.
+    fn most_recent(mut entries: Vec<MemoryEntry>, limit: usize) -> Vec<MemoryEntry> {
+        entries.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+        entries.truncate(limit);
+        entries
+    }
+
     fn category_to_str(category: &MemoryCategory) -> String {
         match category {
             MemoryCategory::Core => "core".to_string(),
@@ -293,7 +300,8 @@ impl Memory for QdrantMemory {
         session_id: Option<&str>,
     ) -> Result<Vec<MemoryEntry>> {
         if query.trim().is_empty() {
-            return self.list(None, session_id).await;
+            let entries = self.list(None, session_id).await?;
+            return Ok(Self::most_recent(entries, limit));
         }
 
         self.ensure_initialized().await?;
@@ -579,6 +587,28 @@ impl Memory for QdrantMemory {
 mod tests {
     use super::*;
 
+    #[test]
+    fn most_recent_orders_newest_first_and_applies_limit() {
+        let entry = |key: &str, timestamp: &str| MemoryEntry {
+            id: key.into(),
+            key: key.into(),
+            content: key.into(),
+            category: MemoryCategory::Core,
+            timestamp: timestamp.into(),
+            session_id: None,
+            score: None,
+        };
+        let entries = vec![
+            entry("old", "2026-01-01T00:00:00+00:00"),
+            entry("new", "2026-03-01T00:00:00+00:00"),
+            entry("mid", "2026-02-01T00:00:00+00:00"),
+        ];
+
+        let recent = QdrantMemory::most_recent(entries, 2);
+        let keys: Vec<&str> = recent.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["new", "mid"]);
+    }
+
     #[test]
     fn category_to_str_maps_known_categories() {
         assert_eq!(QdrantMemory::category_to_str(&MemoryCategory::Core), "core");
@@ -0,0 +1,603 @@
+use super::traits::{CompactionStats, Memory, MemoryCategory, MemoryEntry};
+use anyhow::Result;
+use async_trait::async_trait;
+use std::collections::HashSet;
+
+/// Tiered memory backend composed of an ordered list of backends:
+/// - `get` checks tiers front to back; a hit in a lower tier is copied up.
+/// - `recall` checks tiers front to back and answers from the first tier with
+///   matches; a tier that fails is skipped.
+/// - `list` and `count` cover every tier: authoritative entries first, then
+///   cache entries under keys the authoritative tier doesn't have.
+/// - Writes, deletes and `compact` go to every tier; failures in cache tiers
+///   are logged.
+/// - A tier marked with [`TieredMemory::with_strict_reads`] fails reads
+///   instead of letting the other tiers answer for it.
+///
+/// Backends such as markdown that invent their own keys and can't forget may
+/// be tiers, but their entries are only matched by key on `get`.
+pub struct TieredMemory {
+    tiers: Vec<Box<dyn Memory>>,
+    /// Per tier: return its read errors instead of falling through.
+    strict_reads: Vec<bool>,
+}
+
+impl TieredMemory {
+    pub fn new(tiers: Vec<Box<dyn Memory>>) -> Result<Self> {
+        anyhow::ensure!(
+            !tiers.is_empty(),
+            "tiered memory requires at least one tier"
+        );
+        let strict_reads = vec![false; tiers.len()];
+        Ok(Self {
+            tiers,
+            strict_reads,
+        })
+    }
+
+    /// Fail reads when tier `index` fails instead of answering from the other
+    /// tiers, so an outage of that tier is never covered with possibly stale
+    /// data. Writes still go to every tier as usual.
+    pub fn with_strict_reads(mut self, index: usize) -> Self {
+        if let Some(strict) = self.strict_reads.get_mut(index) {
+            *strict = true;
+        }
+        self
+    }
+
+    fn authoritative(&self) -> &dyn Memory {
+        // `new` guarantees at least one tier.
+        self.tiers[self.tiers.len() - 1].as_ref()
+    }
+
+    fn cache_tiers(&self) -> &[Box<dyn Memory>] {
+        &self.tiers[..self.tiers.len() - 1]
+    }
+}
+
+#[async_trait]
+impl Memory for TieredMemory {
+    fn name(&self) -> &str {
+        "tiered"
+    }
+
+    async fn store(
+        &self,
+        key: &str,
+        content: &str,
+        category: MemoryCategory,
+        session_id: Option<&str>,
+    ) -> Result<()> {
+        // The authoritative tier must succeed before caches are populated.
+        self.authoritative()
+            .store(key, content, category.clone(), session_id)
+            .await?;
+
+        for tier in self.cache_tiers() {
+            if let Err(err) = tier.store(key, content, category.clone(), session_id).await {
+                tracing::warn!(
+                    key,
+                    tier = tier.name(),
+                    error = %err,
+                    "Tiered memory cache write failed; authoritative entry was stored"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn recall(
+        &self,
+        query: &str,
+        limit: usize,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let mut answered = false;
+        let mut last_err = None;
+        for (index, tier) in self.tiers.iter().enumerate() {
+            match tier.recall(query, limit, session_id).await {
+                Ok(entries) if !entries.is_empty() => return Ok(entries),
+                Ok(_) => answered = true,
+                Err(err) if self.strict_reads[index] => return Err(err),
+                Err(err) => {
+                    tracing::warn!(
+                        tier = tier.name(),
+                        error = %err,
+                        "Tiered memory recall failed; trying next tier"
+                    );
+                    last_err = Some(err);
+                }
+            }
+        }
+        match last_err {
+            Some(err) if !answered => Err(err),
+            _ => Ok(Vec::new()),
+        }
+    }
+
+    async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+        let last = self.tiers.len() - 1;
+
+        for (index, tier) in self.tiers.iter().enumerate() {
+            let entry = match tier.get(key).await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => continue,
+                Err(err) if index < last && !self.strict_reads[index] => {
+                    tracing::warn!(
+                        key,
+                        tier = tier.name(),
+                        error = %err,
+                        "Tiered memory cache lookup failed; trying next tier"
+                    );
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+
+            // Read-through: populate the faster tiers that missed.
+            for upper in &self.tiers[..index] {
+                if let Err(err) = upper
+                    .store(
+                        &entry.key,
+                        &entry.content,
+                        entry.category.clone(),
+                        entry.session_id.as_deref(),
+                    )
+                    .await
+                {
+                    tracing::warn!(
+                        key,
+                        tier = upper.name(),
+                        error = %err,
+                        "Tiered memory cache backfill failed"
+                    );
+                }
+            }
+
+            return Ok(Some(entry));
+        }
+
+        Ok(None)
+    }
+
+    async fn list(
+        &self,
+        category: Option<&MemoryCategory>,
+        session_id: Option<&str>,
+    ) -> Result<Vec<MemoryEntry>> {
+        let mut entries = self.authoritative().list(category, session_id).await?;
+        let mut seen: HashSet<String> = entries.iter().map(|entry| entry.key.clone()).collect();
+        for (index, tier) in self.cache_tiers().iter().enumerate() {
+            match tier.list(category, session_id).await {
+                Ok(cached) => {
+                    entries.extend(
+                        cached
+                            .into_iter()
+                            .filter(|entry| seen.insert(entry.key.clone())),
+                    );
+                }
+                Err(err) if self.strict_reads[index] => return Err(err),
+                Err(err) => {
+                    tracing::warn!(
+                        tier = tier.name(),
+                        error = %err,
+                        "Tiered memory cache list failed"
+                    );
+                }
+            }
+        }
+        Ok(entries)
+    }
+
+    async fn forget(&self, key: &str) -> Result<bool> {
+        let mut removed = false;
+        for tier in self.cache_tiers() {
+            match tier.forget(key).await {
+                Ok(hit) => removed |= hit,
+                Err(err) => {
+                    tracing::warn!(
+                        key,
+                        tier = tier.name(),
+                        error = %err,
+                        "Tiered memory cache delete failed"
+                    );
+                }
+            }
+        }
+
+        let authoritative = self.authoritative().forget(key).await?;
+        Ok(authoritative || removed)
+    }
+
+    async fn count(&self) -> Result<usize> {
+        Ok(self.list(None, None).await?.len())
+    }
+
+    async fn health_check(&self) -> bool {
+        for tier in self.cache_tiers() {
+            if !tier.health_check().await {
+                tracing::warn!(
+                    tier = tier.name(),
+                    "Tiered memory cache tier health check failed"
+                );
+            }
+        }
+        self.authoritative().health_check().await
+    }
+
+    async fn reindex(
+        &self,
+        progress_callback: Option<Box<dyn Fn(usize, usize) + Send + Sync>>,
+    ) -> Result<usize> {
+        self.authoritative().reindex(progress_callback).await
+    }
+
+    async fn compact(&self) -> Result<CompactionStats> {
+        let mut total = CompactionStats::default();
+        for tier in self.cache_tiers() {
+            match tier.compact().await {
+                Ok(stats) => {
+                    total.entries_compacted += stats.entries_compacted;
+                    total.bytes_reclaimed += stats.bytes_reclaimed;
+                }
+                Err(err) => {
+                    tracing::warn!(
+                        tier = tier.name(),
+                        error = %err,
+                        "Tiered memory cache compaction failed"
+                    );
+                }
+            }
+        }
+        let stats = self.authoritative().compact().await?;
+        total.entries_compacted += stats.entries_compacted;
+        total.bytes_reclaimed += stats.bytes_reclaimed;
+        Ok(total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::SqliteMemory;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+    use tempfile::TempDir;
+
+    struct StubMemory {
+        entries: Arc<Mutex<HashMap<String, MemoryEntry>>>,
+        fail_reads: bool,
+        compacted: usize,
+    }
+
+    impl StubMemory {
+        fn with_entries(entries: Arc<Mutex<HashMap<String, MemoryEntry>>>) -> Self {
+            Self {
+                entries,
+                fail_reads: false,
+                compacted: 0,
+            }
+        }
+    }
+
+    fn entry(key: &str, content: &str) -> MemoryEntry {
+        MemoryEntry {
+            id: key.to_string(),
+            key: key.to_string(),
+            content: content.to_string(),
+            category: MemoryCategory::Core,
+            timestamp: "2026-01-01T00:00:00Z".to_string(),
+            session_id: None,
+            score: None,
+        }
+    }
+
+    #[async_trait]
+    impl Memory for StubMemory {
+        fn name(&self) -> &str {
+            "stub"
+        }
+
+        async fn store(
+            &self,
+            key: &str,
+            content: &str,
+            category: MemoryCategory,
+            session_id: Option<&str>,
+        ) -> Result<()> {
+            let mut stored = entry(key, content);
+            stored.category = category;
+            stored.session_id = session_id.map(str::to_string);
+            self.entries.lock().unwrap().insert(key.to_string(), stored);
+            Ok(())
+        }
+
+        async fn recall(
+            &self,
+            query: &str,
+            limit: usize,
+            _session_id: Option<&str>,
+        ) -> Result<Vec<MemoryEntry>> {
+            anyhow::ensure!(!self.fail_reads, "reads unavailable");
+            let mut matches: Vec<MemoryEntry> = self
+                .entries
+                .lock()
+                .unwrap()
+                .values()
+                .filter(|entry| entry.content.contains(query))
+                .cloned()
+                .collect();
+            matches.sort_by(|a, b| a.key.cmp(&b.key));
+            matches.truncate(limit);
+            Ok(matches)
+        }
+
+        async fn get(&self, key: &str) -> Result<Option<MemoryEntry>> {
+            anyhow::ensure!(!self.fail_reads, "reads unavailable");
+            Ok(self.entries.lock().unwrap().get(key).cloned())
+        }
+
+        async fn list(
+            &self,
+            _category: Option<&MemoryCategory>,
+            _session_id: Option<&str>,
+        ) -> Result<Vec<MemoryEntry>> {
+            anyhow::ensure!(!self.fail_reads, "reads unavailable");
+            let mut entries: Vec<MemoryEntry> =
+                self.entries.lock().unwrap().values().cloned().collect();
+            entries.sort_by(|a, b| a.key.cmp(&b.key));
+            Ok(entries)
+        }
+
+        async fn forget(&self, key: &str) -> Result<bool> {
+            Ok(self.entries.lock().unwrap().remove(key).is_some())
+        }
+
+        async fn count(&self) -> Result<usize> {
+            Ok(self.entries.lock().unwrap().len())
+        }
+
+        async fn health_check(&self) -> bool {
+            true
+        }
+
+        async fn compact(&self) -> Result<CompactionStats> {
+            Ok(CompactionStats {
+                entries_compacted: self.compacted,
+                bytes_reclaimed: 10,
+            })
+        }
+    }
+
+    fn two_tiers() -> (
+        TieredMemory,
+        Arc<Mutex<HashMap<String, MemoryEntry>>>,
+        Arc<Mutex<HashMap<String, MemoryEntry>>>,
+    ) {
+        let l1 = Arc::new(Mutex::new(HashMap::new()));
+        let l2 = Arc::new(Mutex::new(HashMap::new()));
+        let memory = TieredMemory::new(vec![
+            Box::new(StubMemory::with_entries(Arc::clone(&l1))),
+            Box::new(StubMemory::with_entries(Arc::clone(&l2))),
+        ])
+        .unwrap();
+        (memory, l1, l2)
+    }
+
+    #[test]
+    fn new_rejects_empty_tier_list() {
+        assert!(TieredMemory::new(Vec::new()).is_err());
+    }
+
+    #[tokio::test]
+    async fn get_reads_through_to_lower_tier_and_backfills() {
+        let (memory, l1, l2) = two_tiers();
+        l2.lock()
+            .unwrap()
+            .insert("lang".into(), entry("lang", "Rust"));
+
+        let found = memory.get("lang").await.unwrap().expect("entry in L2");
+        assert_eq!(found.content, "Rust");
+        assert_eq!(l1.lock().unwrap().get("lang").unwrap().content, "Rust");
+    }
+
+    #[tokio::test]
+    async fn store_writes_through_every_tier() {
+        let (memory, l1, l2) = two_tiers();
+        memory
+            .store("lang", "Rust", MemoryCategory::Core, Some("s1"))
+            .await
+            .unwrap();
+
+        for tier in [&l1, &l2] {
+            let stored = tier.lock().unwrap().get("lang").cloned().unwrap();
+            assert_eq!(stored.content, "Rust");
+            assert_eq!(stored.session_id.as_deref(), Some("s1"));
+        }
+    }
+
+    #[tokio::test]
+    async fn forget_removes_from_every_tier() {
+        let (memory, l1, l2) = two_tiers();
+        memory
+            .store("lang", "Rust", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert!(memory.forget("lang").await.unwrap());
+        assert!(l1.lock().unwrap().is_empty());
+        assert!(l2.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn recall_reads_the_first_tier_with_matches() {
+        let (memory, l1, l2) = two_tiers();
+        l1.lock()
+            .unwrap()
+            .insert("a".into(), entry("a", "rust cached"));
+        l2.lock()
+            .unwrap()
+            .insert("a".into(), entry("a", "rust durable"));
+        l2.lock()
+            .unwrap()
+            .insert("b".into(), entry("b", "go durable"));
+
+        let results = memory.recall("rust", 10, None).await.unwrap();
+        let contents: Vec<&str> = results.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(contents, vec!["rust cached"], "an L1 hit is served from L1");
+
+        let results = memory.recall("go", 10, None).await.unwrap();
+        let contents: Vec<&str> = results.iter().map(|e| e.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            vec!["go durable"],
+            "an L1 miss reads through to L2"
+        );
+
+        assert!(memory.recall("python", 10, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn recall_skips_a_failing_tier() {
+        let l2 = Arc::new(Mutex::new(HashMap::new()));
+        l2.lock()
+            .unwrap()
+            .insert("a".into(), entry("a", "rust durable"));
+        let failing = || StubMemory {
+            entries: Arc::new(Mutex::new(HashMap::new())),
+            fail_reads: true,
+            compacted: 0,
+        };
+        let memory = TieredMemory::new(vec![
+            Box::new(failing()),
+            Box::new(StubMemory::with_entries(Arc::clone(&l2))),
+        ])
+        .unwrap();
+
+        let results = memory.recall("rust", 10, None).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].content, "rust durable");
+
+        let all_failing = TieredMemory::new(vec![Box::new(failing())]).unwrap();
+        assert!(all_failing.recall("rust", 10, None).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn strict_tier_failures_fail_reads_but_not_writes() {
+        let l1 = Arc::new(Mutex::new(HashMap::new()));
+        let l2 = Arc::new(Mutex::new(HashMap::new()));
+        l2.lock()
+            .unwrap()
+            .insert("a".into(), entry("a", "rust durable"));
+        let memory = TieredMemory::new(vec![
+            Box::new(StubMemory {
+                fail_reads: true,
+                ..StubMemory::with_entries(Arc::clone(&l1))
+            }),
+            Box::new(StubMemory::with_entries(Arc::clone(&l2))),
+        ])
+        .unwrap()
+        .with_strict_reads(0);
+
+        assert!(memory.recall("rust", 10, None).await.is_err());
+        assert!(memory.get("a").await.is_err());
+        assert!(memory.list(None, None).await.is_err());
+        assert!(memory.count().await.is_err());
+
+        memory
+            .store("b", "written anyway", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        assert!(l1.lock().unwrap().contains_key("b"));
+        assert!(l2.lock().unwrap().contains_key("b"));
+    }
+
+    #[tokio::test]
+    async fn compact_runs_on_every_tier() {
+        let memory = TieredMemory::new(vec![
+            Box::new(StubMemory {
+                compacted: 2,
+                ..StubMemory::with_entries(Arc::default())
+            }),
+            Box::new(StubMemory {
+                compacted: 3,
+                ..StubMemory::with_entries(Arc::default())
+            }),
+        ])
+        .unwrap();
+
+        let stats = memory.compact().await.unwrap();
+        assert_eq!(stats.entries_compacted, 5);
+        assert_eq!(stats.bytes_reclaimed, 20);
+    }
+
+    #[tokio::test]
+    async fn list_and_count_include_cache_only_entries() {
+        let (memory, l1, l2) = two_tiers();
+        l1.lock()
+            .unwrap()
+            .insert("a".into(), entry("a", "cached copy"));
+        l1.lock()
+            .unwrap()
+            .insert("c".into(), entry("c", "cache only"));
+        l2.lock().unwrap().insert("a".into(), entry("a", "durable"));
+        l2.lock().unwrap().insert("b".into(), entry("b", "durable"));
+
+        let listed = memory.list(None, None).await.unwrap();
+        let keys: Vec<&str> = listed.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["a", "b", "c"]);
+        assert_eq!(listed[0].content, "durable");
+        assert_eq!(memory.count().await.unwrap(), 3);
+    }
+
+    fn sqlite_tiers() -> (TempDir, TempDir, TieredMemory) {
+        let cache_dir = TempDir::new().unwrap();
+        let durable_dir = TempDir::new().unwrap();
+        let memory = TieredMemory::new(vec![
+            Box::new(SqliteMemory::new(cache_dir.path()).unwrap()),
+            Box::new(SqliteMemory::new(durable_dir.path()).unwrap()),
+        ])
+        .unwrap();
+        (cache_dir, durable_dir, memory)
+    }
+
+    #[tokio::test]
+    async fn sqlite_tiers_forget_removes_entry_everywhere() {
+        let (cache_dir, durable_dir, memory) = sqlite_tiers();
+        memory
+            .store("lang", "Rust is the language", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        assert!(memory.forget("lang").await.unwrap());
+        assert!(memory.get("lang").await.unwrap().is_none());
+        assert!(memory.recall("Rust", 10, None).await.unwrap().is_empty());
+        for dir in [&cache_dir, &durable_dir] {
+            let tier = SqliteMemory::new(dir.path()).unwrap();
+            assert!(tier.get("lang").await.unwrap().is_none());
+        }
+    }
+
+    #[tokio::test]
+    async fn sqlite_tiers_recall_reads_the_cache_first() {
+        let (_cache_dir, durable_dir, memory) = sqlite_tiers();
+        memory
+            .store("lang", "Rust is the language", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+        SqliteMemory::new(durable_dir.path())
+            .unwrap()
+            .store("tool", "Rust uses cargo", MemoryCategory::Core, None)
+            .await
+            .unwrap();
+
+        let results = memory.recall("Rust", 10, None).await.unwrap();
+        let keys: Vec<&str> = results.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["lang"], "the cache tier has a match");
+
+        let results = memory.recall("cargo", 10, None).await.unwrap();
+        let keys: Vec<&str> = results.iter().map(|e| e.key.as_str()).collect();
+        assert_eq!(keys, vec!["tool"], "a cache miss reads the durable tier");
+    }
+}
@@ -51,6 +51,15 @@ impl std::fmt::Display for MemoryCategory {
     }
 }
 
+/// Outcome of a [`Memory::compact`] pass.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CompactionStats {
+    /// Superseded or removed records dropped from storage.
+    pub entries_compacted: usize,
+    /// Bytes by which storage shrank.
+    pub bytes_reclaimed: u64,
+}
+
 /// Core memory trait — implement for any persistence backend
 #[async_trait]
 pub trait Memory: Send + Sync {
@@ -66,7 +75,15 @@ pub trait Memory: Send + Sync {
         session_id: Option<&str>,
     ) -> anyhow::Result<()>;
 
-    /// Recall memories matching a query (keyword search), optionally scoped to a session
+    /// Recall memories matching a query (keyword search), optionally scoped to a session.
+    ///
+    /// An empty or whitespace-only query means "most recent first": return up
+    /// to `limit` entries ordered by timestamp, newest first. Markdown,
+    /// Postgres (by last update), Qdrant and plugin memory (sorted host-side)
+    /// do this. SQLite and the backends that recall through it (`lucid`,
+    /// `cortex-mem`, `sqlite_qdrant_hybrid`) return no entries, as does
+    /// `none`. Backends whose entries lack a real timestamp must document how
+    /// they rank them.
     async fn recall(
         &self,
         query: &str,
@@ -105,6 +122,12 @@ pub trait Memory: Send + Sync {
         let _ = progress_callback;
         anyhow::bail!("Reindex not supported by {} backend", self.name())
     }
+
+    /// Rewrite storage to drop superseded records. Backends with nothing to
+    /// compact keep this default, which does nothing.
+    async fn compact(&self) -> anyhow::Result<CompactionStats> {
+        Ok(CompactionStats::default())
+    }
 }
 
 #[cfg(test)]
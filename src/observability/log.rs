@@ -113,6 +113,23 @@ impl Observer for LogObserver {
             ObserverMetric::QueueDepth(d) => {
                 info!(depth = d, "metric.queue_depth");
             }
+            ObserverMetric::PluginInFlightCalls { plugin, calls } => {
+                info!(plugin = %plugin, calls, "metric.plugin_in_flight_calls");
+            }
+            ObserverMetric::PluginPayloadBytes {
+                plugin,
+                operation,
+                direction,
+                bytes,
+            } => {
+                info!(
+                    plugin = %plugin,
+                    operation = %operation,
+                    direction = %direction,
+                    bytes,
+                    "metric.plugin_payload_bytes"
+                );
+            }
         }
     }
 
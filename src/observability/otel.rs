@@ -28,6 +28,8 @@ pub struct OtelObserver {
     tokens_used: Counter<u64>,
     active_sessions: Gauge<u64>,
     queue_depth: Gauge<u64>,
+    plugin_in_flight_calls: Gauge<u64>,
+    plugin_payload_bytes: Histogram<u64>,
 }
 
 impl OtelObserver {
@@ -158,6 +160,17 @@ impl OtelObserver {
             .with_description("Current message queue depth")
             .build();
 
+        let plugin_in_flight_calls = meter
+            .u64_gauge("zeroclaw.plugin.in_flight_calls")
+            .with_description("Calls currently running inside each plugin")
+            .build();
+
+        let plugin_payload_bytes = meter
+            .u64_histogram("zeroclaw.plugin.payload_bytes")
+            .with_description("Serialized plugin call request/response size")
+            .with_unit("By")
+            .build();
+
         Ok(Self {
             tracer_provider,
             meter_provider: meter_provider_clone,
@@ -175,6 +188,8 @@ impl OtelObserver {
             tokens_used,
             active_sessions,
             queue_depth,
+            plugin_in_flight_calls,
+            plugin_payload_bytes,
         })
     }
 }
@@ -373,6 +388,25 @@ impl Observer for OtelObserver {
             ObserverMetric::QueueDepth(d) => {
                 self.queue_depth.record(*d as u64, &[]);
             }
+            ObserverMetric::PluginInFlightCalls { plugin, calls } => {
+                self.plugin_in_flight_calls
+                    .record(*calls, &[KeyValue::new("plugin", plugin.clone())]);
+            }
+            ObserverMetric::PluginPayloadBytes {
+                plugin,
+                operation,
+                direction,
+                bytes,
+            } => {
+                self.plugin_payload_bytes.record(
+                    *bytes,
+                    &[
+                        KeyValue::new("plugin", plugin.clone()),
+                        KeyValue::new("operation", operation.clone()),
+                        KeyValue::new("direction", direction.clone()),
+                    ],
+                );
+            }
         }
     }
 
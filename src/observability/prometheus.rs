@@ -23,11 +23,13 @@ pub struct PrometheusObserver {
     agent_duration: HistogramVec,
     tool_duration: HistogramVec,
     request_latency: Histogram,
+    plugin_payload_bytes: HistogramVec,
 
     // Gauges
     tokens_used: prometheus::IntGauge,
     active_sessions: GaugeVec,
     queue_depth: GaugeVec,
+    plugin_in_flight_calls: prometheus::IntGaugeVec,
 }
 
 impl PrometheusObserver {
@@ -121,6 +123,16 @@ impl PrometheusObserver {
         )
         .context("failed to create zeroclaw_request_latency_seconds histogram")?;
 
+        let plugin_payload_bytes = HistogramVec::new(
+            HistogramOpts::new(
+                "zeroclaw_plugin_payload_bytes",
+                "Serialized plugin call request/response size in bytes",
+            )
+            .buckets(prometheus::exponential_buckets(256.0, 4.0, 9)?),
+            &["plugin", "operation", "direction"],
+        )
+        .context("failed to create zeroclaw_plugin_payload_bytes histogram")?;
+
         let tokens_used = prometheus::IntGauge::new(
             "zeroclaw_tokens_used_last",
             "Tokens used in the last request",
@@ -139,6 +151,15 @@ impl PrometheusObserver {
         )
         .context("failed to create zeroclaw_queue_depth gauge")?;
 
+        let plugin_in_flight_calls = prometheus::IntGaugeVec::new(
+            prometheus::Opts::new(
+                "zeroclaw_plugin_in_flight_calls",
+                "Calls currently running inside each plugin",
+            ),
+            &["plugin"],
+        )
+        .context("failed to create zeroclaw_plugin_in_flight_calls gauge")?;
+
         // Register all metrics
         registry
             .register(Box::new(agent_starts.clone()))
@@ -176,6 +197,9 @@ impl PrometheusObserver {
         registry
             .register(Box::new(request_latency.clone()))
             .context("failed to register zeroclaw_request_latency_seconds histogram")?;
+        registry
+            .register(Box::new(plugin_payload_bytes.clone()))
+            .context("failed to register zeroclaw_plugin_payload_bytes histogram")?;
         registry
             .register(Box::new(tokens_used.clone()))
             .context("failed to register zeroclaw_tokens_used_last gauge")?;
@@ -185,6 +209,9 @@ impl PrometheusObserver {
         registry
             .register(Box::new(queue_depth.clone()))
             .context("failed to register zeroclaw_queue_depth gauge")?;
+        registry
+            .register(Box::new(plugin_in_flight_calls.clone()))
+            .context("failed to register zeroclaw_plugin_in_flight_calls gauge")?;
 
         Ok(Self {
             registry,
@@ -200,9 +227,11 @@ impl PrometheusObserver {
             agent_duration,
             tool_duration,
             request_latency,
+            plugin_payload_bytes,
             tokens_used,
             active_sessions,
             queue_depth,
+            plugin_in_flight_calls,
         })
     }
 
@@ -322,6 +351,21 @@ impl Observer for PrometheusObserver {
                     .with_label_values(&[] as &[&str])
                     .set(*d as f64);
             }
+            ObserverMetric::PluginInFlightCalls { plugin, calls } => {
+                self.plugin_in_flight_calls
+                    .with_label_values(&[plugin.as_str()])
+                    .set(i64::try_from(*calls).unwrap_or(i64::MAX));
+            }
+            ObserverMetric::PluginPayloadBytes {
+                plugin,
+                operation,
+                direction,
+                bytes,
+            } => {
+                self.plugin_payload_bytes
+                    .with_label_values(&[plugin.as_str(), operation.as_str(), direction.as_str()])
+                    .observe(*bytes as f64);
+            }
         }
     }
 
@@ -403,6 +447,16 @@ mod tests {
         obs.record_metric(&ObserverMetric::TokensUsed(0));
         obs.record_metric(&ObserverMetric::ActiveSessions(3));
         obs.record_metric(&ObserverMetric::QueueDepth(42));
+        obs.record_metric(&ObserverMetric::PluginInFlightCalls {
+            plugin: "demo".into(),
+            calls: 2,
+        });
+        obs.record_metric(&ObserverMetric::PluginPayloadBytes {
+            plugin: "demo".into(),
+            operation: "zeroclaw_tool_execute".into(),
+            direction: "request".into(),
+            bytes: 512,
+        });
     }
 
     #[test]
@@ -433,6 +487,31 @@ mod tests {
         assert!(output.contains("zeroclaw_request_latency_seconds"));
     }
 
+    #[test]
+    fn plugin_payload_bytes_are_bucketed_per_plugin_operation_and_direction() {
+        let obs = test_observer();
+        for bytes in [100, 5_000] {
+            obs.record_metric(&ObserverMetric::PluginPayloadBytes {
+                plugin: "kv".into(),
+                operation: "zeroclaw_memory_call".into(),
+                direction: "response".into(),
+                bytes,
+            });
+        }
+
+        let output = obs.encode();
+        let labels = r#"direction="response",operation="zeroclaw_memory_call",plugin="kv""#;
+        assert!(output.contains(&format!(
+            "zeroclaw_plugin_payload_bytes_count{{{labels}}} 2"
+        )));
+        assert!(output.contains(&format!(
+            "zeroclaw_plugin_payload_bytes_sum{{{labels}}} 5100"
+        )));
+        assert!(output.contains(&format!(
+            "zeroclaw_plugin_payload_bytes_bucket{{{labels},le=\"256\"}} 1"
+        )));
+    }
+
     #[test]
     fn counters_increment_correctly() {
         let obs = test_observer();
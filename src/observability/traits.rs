@@ -90,6 +90,17 @@ pub enum ObserverMetric {
     ActiveSessions(u64),
     /// Current depth of the inbound message queue.
     QueueDepth(u64),
+    /// Calls currently running inside one plugin's module.
+    PluginInFlightCalls { plugin: String, calls: u64 },
+    /// Serialized size of one plugin call's request or response.
+    PluginPayloadBytes {
+        plugin: String,
+        /// Guest export that was called (e.g., `"zeroclaw_memory_call"`).
+        operation: String,
+        /// `"request"` or `"response"`.
+        direction: String,
+        bytes: u64,
+    },
 }
 
 /// Core observability trait for recording agent runtime telemetry.
@@ -0,0 +1,77 @@
+use super::runtime::{self, PluginRuntime};
+use crate::config::Config;
+use anyhow::{bail, Result};
+use console::style;
+
+/// Handle `zeroclaw plugins <subcommand>` CLI commands.
+pub async fn handle_command(command: crate::PluginCommands, config: &Config) -> Result<()> {
+    runtime::initialize_from_config(&config.plugins)?;
+    match command {
+        crate::PluginCommands::Status => handle_status().await,
+        crate::PluginCommands::Validate => handle_validate(),
+    }
+}
+
+async fn handle_status() -> Result<()> {
+    let report = PluginRuntime::new().health_report().await;
+    if report.is_empty() {
+        println!("No plugins found in [plugins] load_paths.");
+        return Ok(());
+    }
+
+    println!("{:<24} {:<9} {:<10} MODULE", "PLUGIN", "ENABLED", "HEALTH");
+    for health in &report {
+        // Pad before styling so escape codes don't skew the columns.
+        let status = match health.healthy {
+            Some(true) => style(format!("{:<10}", "healthy")).green().bold(),
+            Some(false) => style(format!("{:<10}", "unhealthy")).red().bold(),
+            None => style(format!("{:<10}", "-")).dim(),
+        };
+        println!(
+            "{:<24} {:<9} {status} {}",
+            health.id,
+            if health.enabled { "yes" } else { "no" },
+            health.module_path
+        );
+        if let Some(error) = &health.last_error {
+            println!("    {}", style(error).yellow());
+        }
+    }
+    Ok(())
+}
+
+fn handle_validate() -> Result<()> {
+    let report = PluginRuntime::new().validate_all();
+    if report.is_empty() {
+        println!("No enabled plugins to validate.");
+        return Ok(());
+    }
+
+    let mut failed = 0;
+    for validation in &report {
+        if validation.passed() {
+            println!("{} {}", style("✓").green().bold(), validation.id);
+            continue;
+        }
+        failed += 1;
+        println!(
+            "{} {} ({})",
+            style("✗").red().bold(),
+            validation.id,
+            validation.module_path
+        );
+        for error in &validation.errors {
+            println!("    {error}");
+        }
+        if !validation.missing_exports.is_empty() {
+            println!(
+                "    missing exports: {}",
+                validation.missing_exports.join(", ")
+            );
+        }
+    }
+    if failed > 0 {
+        bail!("{failed} of {} plugins failed validation", report.len());
+    }
+    Ok(())
+}
@@ -20,7 +20,7 @@ use super::registry::{
 use super::traits::{Plugin, PluginApi, PluginLogger};
 
 /// Resolve whether a discovered plugin should be enabled.
-fn resolve_enable(id: &str, cfg: &PluginsConfig) -> Result<(), String> {
+pub(super) fn resolve_enable(id: &str, cfg: &PluginsConfig) -> Result<(), String> {
     if !cfg.enabled {
         return Err("plugins disabled".into());
     }
@@ -295,6 +295,7 @@ mod tests {
             wit_packages: vec![],
             tools: vec![],
             providers: vec![],
+            idempotent: false,
         }
     }
 
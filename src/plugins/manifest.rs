@@ -12,8 +12,12 @@ use std::path::Path;
 use super::traits::PluginCapability;
 
 const SUPPORTED_WIT_MAJOR: u64 = 1;
-const SUPPORTED_WIT_PACKAGES: [&str; 3] =
-    ["zeroclaw:hooks", "zeroclaw:tools", "zeroclaw:providers"];
+const SUPPORTED_WIT_PACKAGES: [&str; 4] = [
+    "zeroclaw:hooks",
+    "zeroclaw:tools",
+    "zeroclaw:providers",
+    "zeroclaw:memory",
+];
 
 /// Validation profile for plugin manifests.
 ///
@@ -73,6 +77,11 @@ pub struct PluginManifest {
     /// Manifest-declared providers (runtime placeholder wiring for now).
     #[serde(default)]
     pub providers: Vec<String>,
+    /// Whether calling an export twice with the same payload is safe. Only
+    /// idempotent plugins have timed-out calls retried: the host can't tell
+    /// whether an interrupted guest already had side effects.
+    #[serde(default)]
+    pub idempotent: bool,
 }
 
 /// Result of attempting to load a manifest from a directory.
@@ -147,6 +156,7 @@ fn required_wit_package_for_capability(capability: &PluginCapability) -> &'stati
         PluginCapability::Hooks | PluginCapability::ModifyToolResults => "zeroclaw:hooks",
         PluginCapability::Tools => "zeroclaw:tools",
         PluginCapability::Providers => "zeroclaw:providers",
+        PluginCapability::Memory => "zeroclaw:memory",
     }
 }
 
@@ -229,6 +239,143 @@ impl PluginManifest {
     pub fn is_valid(&self) -> bool {
         validate_manifest(self).is_ok()
     }
+
+    /// Check that `config_schema` sticks to the JSON Schema subset
+    /// [`Self::validate_config`] understands: `type` (a name or a list of
+    /// names), `required`, `properties`, `items` and `enum`, plus annotations
+    /// such as `description`. A schema relying on any other keyword would be
+    /// silently unenforced, so it is rejected instead.
+    pub fn check_config_schema(&self) -> anyhow::Result<()> {
+        let Some(schema) = &self.config_schema else {
+            return Ok(());
+        };
+        check_schema_keywords(&serde_json::to_value(schema)?, "config_schema")
+            .map_err(|error| anyhow::anyhow!("plugin '{}' {error}", self.id))
+    }
+
+    /// Check a plugin's `[plugins.entries.<id>].config` table against the
+    /// manifest's `config_schema`. Manifests without a schema accept anything;
+    /// schemas outside the supported subset are rejected as by
+    /// [`Self::check_config_schema`]. As in JSON Schema, `integer` accepts
+    /// any number with a zero fractional part, so `3.0` is an integer.
+    pub fn validate_config(&self, config: &Value) -> anyhow::Result<()> {
+        let Some(schema) = &self.config_schema else {
+            return Ok(());
+        };
+        self.check_config_schema()?;
+        let schema = serde_json::to_value(schema)?;
+        check_schema(&schema, config, "config")
+            .map_err(|error| anyhow::anyhow!("plugin '{}' {error}", self.id))
+    }
+}
+
+/// Keywords [`check_schema`] enforces.
+const SCHEMA_KEYWORDS: &[&str] = &["type", "required", "properties", "items", "enum"];
+/// Keywords that only annotate a schema and never affect validation.
+const SCHEMA_ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+];
+
+fn check_schema_keywords(schema: &Value, path: &str) -> Result<(), String> {
+    let Value::Object(map) = schema else {
+        return Err(format!("{path} must be a table"));
+    };
+    for (keyword, value) in map {
+        if SCHEMA_ANNOTATIONS.contains(&keyword.as_str()) {
+            continue;
+        }
+        if !SCHEMA_KEYWORDS.contains(&keyword.as_str()) {
+            return Err(format!(
+                "{path} uses unsupported keyword '{keyword}' (supported: {})",
+                SCHEMA_KEYWORDS.join(", ")
+            ));
+        }
+        match keyword.as_str() {
+            "properties" => {
+                let Value::Object(properties) = value else {
+                    return Err(format!("{path}.properties must be a table"));
+                };
+                for (key, property) in properties {
+                    check_schema_keywords(property, &format!("{path}.properties.{key}"))?;
+                }
+            }
+            "items" => check_schema_keywords(value, &format!("{path}.items"))?,
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn json_type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(n) if n.is_i64() || n.is_u64() => "integer",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn type_matches(expected: &str, value: &Value) -> bool {
+    match (expected, value) {
+        ("number", Value::Number(_)) => true,
+        ("integer", Value::Number(n)) => {
+            n.is_i64() || n.is_u64() || n.as_f64().is_some_and(|f| f.fract() == 0.0)
+        }
+        _ => json_type_name(value) == expected,
+    }
+}
+
+fn check_schema(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let expected: Vec<&str> = match schema.get("type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    };
+    if !expected.is_empty() && !expected.iter().any(|name| type_matches(name, value)) {
+        return Err(format!(
+            "{path}: expected {}, found {}",
+            expected.join(" or "),
+            json_type_name(value)
+        ));
+    }
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            return Err(format!(
+                "{path}: {value} is not one of {}",
+                Value::from(allowed.clone())
+            ));
+        }
+    }
+    if let Value::Object(map) = value {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !map.contains_key(key) {
+                    return Err(format!("{path}: missing required key '{key}'"));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, property) in properties {
+                if let Some(child) = map.get(key) {
+                    check_schema(property, child, &format!("{path}.{key}"))?;
+                }
+            }
+        }
+    }
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            check_schema(item_schema, item, &format!("{path}[{index}]"))?;
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -326,6 +473,7 @@ id = "  "
             wit_packages: vec!["zeroclaw:hooks@1.0.0".into()],
             tools: vec![],
             providers: vec![],
+            idempotent: false,
         };
         assert!(valid.is_valid());
     }
@@ -343,6 +491,7 @@ id = "  "
             wit_packages: vec!["zeroclaw:unknown@1.0.0".into()],
             tools: vec![],
             providers: vec![],
+            idempotent: false,
         };
         assert!(validate_manifest(&manifest).is_err());
     }
@@ -360,6 +509,7 @@ id = "  "
             wit_packages: vec!["zeroclaw:hooks@1.0.0".into()],
             tools: vec![],
             providers: vec![],
+            idempotent: false,
         };
         assert!(validate_manifest(&manifest).is_err());
     }
@@ -377,6 +527,7 @@ id = "  "
             wit_packages: vec![],
             tools: vec![],
             providers: vec![],
+            idempotent: false,
         };
         assert!(
             validate_manifest_with_profile(&manifest, ManifestValidationProfile::SchemaOnly)
@@ -397,6 +548,7 @@ id = "  "
             wit_packages: vec!["zeroclaw:hooks@1.0.0".into()],
             tools: vec![],
             providers: vec![],
+            idempotent: false,
         };
         assert!(validate_manifest(&manifest).is_err());
     }
@@ -414,6 +566,7 @@ id = "  "
             wit_packages: vec!["zeroclaw:hooks@1.0.0".into()],
             tools: vec![],
             providers: vec![],
+            idempotent: false,
         };
         assert!(validate_manifest(&manifest).is_err());
     }
@@ -438,6 +591,7 @@ id = "  "
                 }),
             }],
             providers: vec![],
+            idempotent: false,
         };
         assert!(validate_manifest(&manifest).is_err());
     }
@@ -455,7 +609,138 @@ id = "  "
             wit_packages: vec!["zeroclaw:hooks@1.0.0".into()],
             tools: vec![],
             providers: vec!["demo_provider".into()],
+            idempotent: false,
         };
         assert!(validate_manifest(&manifest).is_err());
     }
+
+    #[test]
+    fn validate_config_checks_declared_schema() {
+        let manifest: PluginManifest = toml::from_str(
+            r#"
+id = "settings-demo"
+module_path = "plugins/demo.wasm"
+
+[config_schema]
+type = "object"
+required = ["url"]
+
+[config_schema.properties.url]
+type = "string"
+
+[config_schema.properties.retries]
+type = "integer"
+"#,
+        )
+        .unwrap();
+
+        manifest
+            .validate_config(&serde_json::json!({ "url": "https://example.com", "retries": 3 }))
+            .expect("valid settings");
+
+        let missing = manifest
+            .validate_config(&serde_json::json!({ "uri": "https://example.com" }))
+            .unwrap_err();
+        assert!(missing.to_string().contains("missing required key 'url'"));
+
+        let wrong_type = manifest
+            .validate_config(&serde_json::json!({ "url": 42 }))
+            .unwrap_err();
+        assert!(wrong_type
+            .to_string()
+            .contains("config.url: expected string, found integer"));
+    }
+
+    #[test]
+    fn validate_config_handles_type_lists_and_integral_floats() {
+        let manifest: PluginManifest = toml::from_str(
+            r#"
+id = "types-demo"
+module_path = "plugins/demo.wasm"
+
+[config_schema.properties.retries]
+type = "integer"
+
+[config_schema.properties.proxy]
+type = ["string", "null"]
+"#,
+        )
+        .unwrap();
+
+        manifest
+            .validate_config(&serde_json::json!({ "retries": 3.0, "proxy": null }))
+            .expect("3.0 is an integer and null is allowed");
+        manifest
+            .validate_config(&serde_json::json!({ "proxy": "http://proxy:8080" }))
+            .expect("string is allowed");
+
+        let fractional = manifest
+            .validate_config(&serde_json::json!({ "retries": 2.5 }))
+            .unwrap_err();
+        assert!(fractional
+            .to_string()
+            .contains("config.retries: expected integer, found number"));
+
+        let wrong = manifest
+            .validate_config(&serde_json::json!({ "proxy": 8080 }))
+            .unwrap_err();
+        assert!(wrong
+            .to_string()
+            .contains("config.proxy: expected string or null, found integer"));
+    }
+
+    #[test]
+    fn config_schema_with_unsupported_keywords_is_rejected() {
+        let manifest: PluginManifest = toml::from_str(
+            r#"
+id = "bounds-demo"
+module_path = "plugins/demo.wasm"
+
+[config_schema]
+type = "object"
+description = "annotations are fine"
+
+[config_schema.properties.port]
+type = "integer"
+minimum = 1
+"#,
+        )
+        .unwrap();
+
+        let err = manifest.check_config_schema().unwrap_err().to_string();
+        assert!(
+            err.contains("config_schema.properties.port uses unsupported keyword 'minimum'"),
+            "{err}"
+        );
+        assert!(manifest
+            .validate_config(&serde_json::json!({ "port": 0 }))
+            .is_err());
+
+        let supported: PluginManifest = toml::from_str(
+            r#"
+id = "list-demo"
+module_path = "plugins/demo.wasm"
+
+[config_schema.properties.hosts]
+type = "array"
+description = "upstream hosts"
+
+[config_schema.properties.hosts.items]
+type = "string"
+"#,
+        )
+        .unwrap();
+        supported.check_config_schema().expect("subset only");
+    }
+
+    #[test]
+    fn validate_config_accepts_anything_without_schema() {
+        let manifest = PluginManifest {
+            id: "no-schema".into(),
+            ..PluginManifest::default()
+        };
+        manifest
+            .validate_config(&serde_json::json!({ "anything": [1, "two"] }))
+            .expect("no schema declared");
+    }
 }
@@ -38,6 +38,7 @@
 //! ```
 
 pub mod bridge;
+pub mod cli;
 pub mod discovery;
 pub mod loader;
 pub mod manifest;
@@ -54,9 +55,11 @@ pub use manifest::{PluginManifest, PLUGIN_MANIFEST_FILENAME};
 #[allow(unused_imports)]
 pub use registry::{
     DiagnosticLevel, PluginDiagnostic, PluginHookRegistration, PluginOrigin, PluginRecord,
-    PluginRegistry, PluginStatus, PluginToolRegistration,
+    PluginRegistry, PluginSettings, PluginStatus, PluginToolRegistration, ReconfigureSummary,
 };
 #[allow(unused_imports)]
+pub use runtime::{QuarantinePolicy, RetryPolicy};
+#[allow(unused_imports)]
 pub use traits::{Plugin, PluginApi, PluginCapability, PluginLogger};
 
 #[cfg(test)]
@@ -76,6 +79,7 @@ mod tests {
             wit_packages: vec![],
             tools: vec![],
             providers: vec![],
+            idempotent: false,
         };
         assert_eq!(PLUGIN_MANIFEST_FILENAME, "zeroclaw.plugin.toml");
     }
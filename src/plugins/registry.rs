@@ -3,6 +3,7 @@
 //! Mirrors OpenClaw's `PluginRegistry` / `createPluginRegistry()`.
 
 use std::collections::{HashMap, HashSet};
+use std::time::Instant;
 
 use crate::hooks::HookHandler;
 use crate::tools::traits::Tool;
@@ -71,6 +72,38 @@ pub struct PluginHookRegistration {
     pub handler: Box<dyn HookHandler>,
 }
 
+/// Per-plugin runtime settings from `[plugins.entries.<id>]`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PluginSettings {
+    /// Cap on concurrent calls into the plugin's module, on top of the global
+    /// limit. `None` leaves only the global limit.
+    pub max_concurrent_calls: Option<usize>,
+    /// Per-call budget in milliseconds. `None` uses the runtime default.
+    pub timeout_ms: Option<u64>,
+    /// Pinned hex SHA-256 of the module file. `None` disables the check.
+    pub sha256: Option<String>,
+    /// Whether other memory tiers may answer reads while this plugin fails.
+    /// `None` means yes.
+    pub read_fallback: Option<bool>,
+    /// Memory categories the plugin may store. `None` allows all.
+    pub allowed_categories: Option<Vec<String>>,
+}
+
+/// Changes applied by [`PluginRegistry::reconfigure`], as sorted plugin IDs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReconfigureSummary {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    /// Plugins whose manifest or `[plugins.entries.<id>.config]` changed.
+    pub updated: Vec<String>,
+}
+
+impl ReconfigureSummary {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.updated.is_empty()
+    }
+}
+
 /// The plugin registry — the central collection of everything plugins contribute.
 ///
 /// Analogous to OpenClaw's `PluginRegistry` returned by `loadPlugins()`.
@@ -80,10 +113,18 @@ pub struct PluginRegistry {
     pub hooks: Vec<PluginHookRegistration>,
     pub diagnostics: Vec<PluginDiagnostic>,
     manifests: HashMap<String, PluginManifest>,
+    /// Manifests found on disk but disabled by config; never routed.
+    disabled: HashMap<String, PluginManifest>,
     manifest_tools: Vec<PluginToolManifest>,
     manifest_providers: HashSet<String>,
-    tool_modules: HashMap<String, String>,
-    provider_modules: HashMap<String, String>,
+    tool_plugins: HashMap<String, String>,
+    provider_plugins: HashMap<String, String>,
+    plugin_configs: HashMap<String, serde_json::Value>,
+    settings: HashMap<String, PluginSettings>,
+    /// Plugins left out of routing, with the instant their quarantine ends
+    /// (`None` until released explicitly).
+    quarantined: HashMap<String, Option<Instant>>,
+    generation: u64,
 }
 
 impl PluginRegistry {
@@ -94,10 +135,15 @@ impl PluginRegistry {
             hooks: Vec::new(),
             diagnostics: Vec::new(),
             manifests: HashMap::new(),
+            disabled: HashMap::new(),
             manifest_tools: Vec::new(),
             manifest_providers: HashSet::new(),
-            tool_modules: HashMap::new(),
-            provider_modules: HashMap::new(),
+            tool_plugins: HashMap::new(),
+            provider_plugins: HashMap::new(),
+            plugin_configs: HashMap::new(),
+            settings: HashMap::new(),
+            quarantined: HashMap::new(),
+            generation: 0,
         }
     }
 
@@ -125,6 +171,19 @@ impl PluginRegistry {
         self.all_manifests()
     }
 
+    pub fn manifest(&self, id: &str) -> Option<&PluginManifest> {
+        self.manifests.get(id)
+    }
+
+    /// Record a manifest that config disables, for status reporting only.
+    pub fn register_disabled(&mut self, manifest: PluginManifest) {
+        self.disabled.insert(manifest.id.clone(), manifest);
+    }
+
+    pub fn disabled_manifests(&self) -> Vec<&PluginManifest> {
+        self.disabled.values().collect()
+    }
+
     pub fn all_manifests(&self) -> Vec<&PluginManifest> {
         self.manifests.values().collect()
     }
@@ -146,33 +205,228 @@ impl PluginRegistry {
     }
 
     pub fn tool_module_path(&self, tool: &str) -> Option<&str> {
-        self.tool_modules.get(tool).map(String::as_str)
+        self.plugin_module_path(self.tool_plugin_id(tool)?)
     }
 
     pub fn provider_module_path(&self, provider: &str) -> Option<&str> {
-        self.provider_modules.get(provider).map(String::as_str)
+        self.plugin_module_path(self.provider_plugin_id(provider)?)
+    }
+
+    /// Id of the plugin that serves `tool`.
+    pub fn tool_plugin_id(&self, tool: &str) -> Option<&str> {
+        self.tool_plugins.get(tool).map(String::as_str)
+    }
+
+    /// Id of the plugin that serves `provider`.
+    pub fn provider_plugin_id(&self, provider: &str) -> Option<&str> {
+        self.provider_plugins.get(provider).map(String::as_str)
+    }
+
+    fn plugin_module_path(&self, id: &str) -> Option<&str> {
+        self.manifests
+            .get(id)
+            .map(|manifest| manifest.module_path.as_str())
+    }
+
+    /// Plugin-specific config table recorded for `id`, if one was configured.
+    pub fn plugin_config(&self, id: &str) -> Option<&serde_json::Value> {
+        self.plugin_configs.get(id)
+    }
+
+    pub(crate) fn set_plugin_config(&mut self, id: &str, config: serde_json::Value) {
+        self.plugin_configs.insert(id.to_string(), config);
+    }
+
+    pub(crate) fn set_settings(&mut self, id: &str, settings: PluginSettings) {
+        self.settings.insert(id.to_string(), settings);
+    }
+
+    /// Runtime settings recorded for plugin `id`.
+    pub fn settings(&self, id: &str) -> Option<&PluginSettings> {
+        self.settings.get(id)
+    }
+
+    /// Digest pinned for `module_path` by any plugin loading it. Plugins whose
+    /// pin didn't match at load are refused, so all remaining pins agree.
+    pub fn module_sha256(&self, module_path: &str) -> Option<&str> {
+        let mut ids: Vec<&String> = self
+            .manifests
+            .values()
+            .filter(|manifest| manifest.module_path == module_path)
+            .map(|manifest| &manifest.id)
+            .collect();
+        ids.sort();
+        ids.into_iter()
+            .find_map(|id| self.settings.get(id)?.sha256.as_deref())
+    }
+
+    /// Counter bumped every time [`Self::reconfigure`] or
+    /// [`Self::swap_module`] changes the registry.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Point plugin `id` at `module_path`, pinning it to `sha256` when given,
+    /// and return the module path it used before. The swap only redirects
+    /// routing; verifying and compiling the new module is up to the caller.
+    /// The registry does not remember the swap, so reconfiguring it from
+    /// freshly loaded manifests reverts it unless the caller re-applies it.
+    pub fn swap_module(
+        &mut self,
+        id: &str,
+        module_path: &str,
+        sha256: Option<String>,
+    ) -> anyhow::Result<String> {
+        let manifest = self
+            .manifests
+            .get_mut(id)
+            .ok_or_else(|| anyhow::anyhow!("plugin '{id}' is not registered"))?;
+        let previous = std::mem::replace(&mut manifest.module_path, module_path.to_string());
+        let settings = self.settings.entry(id.to_string()).or_default();
+        settings.sha256 = sha256.map(|digest| digest.trim().to_ascii_lowercase());
+        self.rebuild_indexes();
+        self.generation += 1;
+        Ok(previous)
+    }
+
+    /// Take plugin `id` out of tool and provider routing until `until`, or
+    /// until [`Self::release`] when `None`. Its manifest and settings stay
+    /// registered. Returns `false` if `id` is unknown or already quarantined.
+    pub fn quarantine(&mut self, id: &str, until: Option<Instant>) -> bool {
+        if !self.manifests.contains_key(id) || self.quarantined.contains_key(id) {
+            return false;
+        }
+        self.quarantined.insert(id.to_string(), until);
+        self.rebuild_indexes();
+        self.generation += 1;
+        true
+    }
+
+    /// Put a quarantined plugin back into routing. Returns `false` if it was
+    /// not quarantined.
+    pub fn release(&mut self, id: &str) -> bool {
+        if self.quarantined.remove(id).is_none() {
+            return false;
+        }
+        self.rebuild_indexes();
+        self.generation += 1;
+        true
+    }
+
+    pub fn is_quarantined(&self, id: &str) -> bool {
+        self.quarantined.contains_key(id)
+    }
+
+    /// Whether any quarantine with a cooldown has ended by `now`.
+    pub fn has_expired_quarantine(&self, now: Instant) -> bool {
+        self.quarantined
+            .values()
+            .any(|until| until.is_some_and(|until| until <= now))
+    }
+
+    /// Release every quarantine whose cooldown has ended by `now` and return
+    /// the released plugin ids, sorted.
+    pub fn release_expired(&mut self, now: Instant) -> Vec<String> {
+        let mut released: Vec<String> = self
+            .quarantined
+            .iter()
+            .filter(|(_, until)| until.is_some_and(|until| until <= now))
+            .map(|(id, _)| id.clone())
+            .collect();
+        if released.is_empty() {
+            return released;
+        }
+        released.sort();
+        for id in &released {
+            self.quarantined.remove(id);
+        }
+        self.rebuild_indexes();
+        self.generation += 1;
+        released
+    }
+
+    /// Re-ingest a registry freshly loaded from an updated `[plugins]` config
+    /// without restarting.
+    ///
+    /// `next` is diffed against the current state: new plugins are added,
+    /// dropped or disabled ones are removed from routing, and manifest or
+    /// settings changes replace the existing entry. The generation is bumped
+    /// only when something changed. Plugins refused while loading `next` are
+    /// listed in [`Self::diagnostics`]. Quarantines survive, except for
+    /// plugins the new config drops.
+    pub fn reconfigure(&mut self, next: PluginRegistry) -> ReconfigureSummary {
+        // Refusals and disabled plugins are reported on every load, whether
+        // or not routing changes.
+        self.diagnostics = next.diagnostics;
+        self.disabled = next.disabled;
+        let mut summary = ReconfigureSummary::default();
+
+        for (id, manifest) in &next.manifests {
+            match self.manifests.get(id) {
+                None => summary.added.push(id.clone()),
+                Some(current) => {
+                    let manifest_changed =
+                        serde_json::to_value(current).ok() != serde_json::to_value(manifest).ok();
+                    let settings_changed = self.plugin_configs.get(id)
+                        != next.plugin_configs.get(id)
+                        || self.settings.get(id) != next.settings.get(id);
+                    if manifest_changed || settings_changed {
+                        summary.updated.push(id.clone());
+                    }
+                }
+            }
+        }
+        summary.removed = self
+            .manifests
+            .keys()
+            .filter(|id| !next.manifests.contains_key(*id))
+            .cloned()
+            .collect();
+
+        summary.added.sort();
+        summary.removed.sort();
+        summary.updated.sort();
+        if summary.is_empty() {
+            return summary;
+        }
+
+        self.manifests = next.manifests;
+        self.plugin_configs = next.plugin_configs;
+        self.settings = next.settings;
+        let manifests = &self.manifests;
+        self.quarantined.retain(|id, _| manifests.contains_key(id));
+        self.rebuild_indexes();
+        self.generation += 1;
+        summary
     }
 
     fn rebuild_indexes(&mut self) {
         self.manifest_tools.clear();
         self.manifest_providers.clear();
-        self.tool_modules.clear();
-        self.provider_modules.clear();
-
-        for manifest in self.manifests.values() {
-            let module_path = manifest.module_path.clone();
+        self.tool_plugins.clear();
+        self.provider_plugins.clear();
+
+        // Walk manifests in id order so the first claimant of a tool or
+        // provider name is the same on every rebuild.
+        let mut manifests: Vec<&PluginManifest> = self
+            .manifests
+            .values()
+            .filter(|manifest| !self.quarantined.contains_key(&manifest.id))
+            .collect();
+        manifests.sort_by(|a, b| a.id.cmp(&b.id));
+        for manifest in manifests {
             self.manifest_tools.extend(manifest.tools.iter().cloned());
             for tool in &manifest.tools {
-                self.tool_modules
+                self.tool_plugins
                     .entry(tool.name.clone())
-                    .or_insert_with(|| module_path.clone());
+                    .or_insert_with(|| manifest.id.clone());
             }
             for provider in &manifest.providers {
                 let provider = provider.trim().to_string();
                 self.manifest_providers.insert(provider.clone());
-                self.provider_modules
+                self.provider_plugins
                     .entry(provider)
-                    .or_insert_with(|| module_path.clone());
+                    .or_insert_with(|| manifest.id.clone());
             }
         }
     }
@@ -194,10 +448,15 @@ impl Clone for PluginRegistry {
             hooks: Vec::new(),
             diagnostics: self.diagnostics.clone(),
             manifests: self.manifests.clone(),
+            disabled: self.disabled.clone(),
             manifest_tools: self.manifest_tools.clone(),
             manifest_providers: self.manifest_providers.clone(),
-            tool_modules: self.tool_modules.clone(),
-            provider_modules: self.provider_modules.clone(),
+            tool_plugins: self.tool_plugins.clone(),
+            provider_plugins: self.provider_plugins.clone(),
+            plugin_configs: self.plugin_configs.clone(),
+            settings: self.settings.clone(),
+            quarantined: self.quarantined.clone(),
+            generation: self.generation,
         }
     }
 }
@@ -205,6 +464,43 @@ impl Clone for PluginRegistry {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::{PluginEntryConfig, PluginsConfig};
+    use crate::plugins::runtime::PluginRuntime;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    fn write_manifest(dir: &std::path::Path, id: &str, version: &str) {
+        std::fs::write(
+            dir.join(format!("{id}.plugin.toml")),
+            format!(
+                r#"
+id = "{id}"
+version = "{version}"
+module_path = "plugins/{id}.wasm"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+
+[[tools]]
+name = "{id}_tool"
+description = "{id} tool"
+"#
+            ),
+        )
+        .expect("write manifest");
+    }
+
+    fn config_for(dir: &TempDir) -> PluginsConfig {
+        PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        }
+    }
+
+    fn load(config: &PluginsConfig) -> PluginRegistry {
+        PluginRuntime::new()
+            .load_registry_from_config(config)
+            .expect("load registry")
+    }
 
     fn manifest_with(id: &str, tool_name: &str, provider: &str) -> PluginManifest {
         PluginManifest {
@@ -225,6 +521,7 @@ mod tests {
                 }),
             }],
             providers: vec![provider.to_string()],
+            idempotent: false,
         }
     }
 
@@ -297,4 +594,194 @@ mod tests {
         assert!(reg.has_provider("provider_v2_for_replace_test"));
         assert!(!reg.has_provider("provider_v1_for_replace_test"));
     }
+
+    #[test]
+    fn reconfigure_adds_removes_and_updates_plugins() {
+        let dir = TempDir::new().expect("temp dir");
+        write_manifest(dir.path(), "alpha", "1.0.0");
+        write_manifest(dir.path(), "beta", "1.0.0");
+        let mut cfg = config_for(&dir);
+
+        let mut reg = PluginRegistry::new();
+        let summary = reg.reconfigure(load(&cfg));
+        assert_eq!(summary.added, vec!["alpha", "beta"]);
+        assert_eq!(reg.generation(), 1);
+        assert!(reg.tool_module_path("alpha_tool").is_some());
+
+        let unchanged = reg.reconfigure(load(&cfg));
+        assert!(unchanged.is_empty());
+        assert_eq!(reg.generation(), 1);
+
+        std::fs::remove_file(dir.path().join("beta.plugin.toml")).expect("remove beta");
+        write_manifest(dir.path(), "alpha", "2.0.0");
+        write_manifest(dir.path(), "gamma", "1.0.0");
+        let summary = reg.reconfigure(load(&cfg));
+        assert_eq!(summary.added, vec!["gamma"]);
+        assert_eq!(summary.removed, vec!["beta"]);
+        assert_eq!(summary.updated, vec!["alpha"]);
+        assert_eq!(reg.generation(), 2);
+        assert!(reg.tool_module_path("beta_tool").is_none());
+        assert!(reg.tool_module_path("gamma_tool").is_some());
+
+        cfg.entries.insert(
+            "alpha".into(),
+            PluginEntryConfig {
+                config: serde_json::json!({ "greeting": "hi" }),
+                ..PluginEntryConfig::default()
+            },
+        );
+        let summary = reg.reconfigure(load(&cfg));
+        assert_eq!(summary.updated, vec!["alpha"]);
+        assert_eq!(
+            reg.plugin_config("alpha"),
+            Some(&serde_json::json!({ "greeting": "hi" }))
+        );
+        assert_eq!(reg.generation(), 3);
+    }
+
+    #[test]
+    fn reconfigure_toggles_enabled_state() {
+        let dir = TempDir::new().expect("temp dir");
+        write_manifest(dir.path(), "alpha", "1.0.0");
+        let mut cfg = config_for(&dir);
+        let mut reg = PluginRegistry::new();
+        reg.reconfigure(load(&cfg));
+
+        cfg.entries.insert(
+            "alpha".into(),
+            PluginEntryConfig {
+                enabled: Some(false),
+                ..PluginEntryConfig::default()
+            },
+        );
+        let summary = reg.reconfigure(load(&cfg));
+        assert_eq!(summary.removed, vec!["alpha"]);
+        assert!(reg.is_empty());
+
+        cfg.entries.remove("alpha");
+        let summary = reg.reconfigure(load(&cfg));
+        assert_eq!(summary.added, vec!["alpha"]);
+        assert!(reg.tool_module_path("alpha_tool").is_some());
+    }
+
+    #[test]
+    fn reconfigure_tracks_per_plugin_timeouts() {
+        let dir = TempDir::new().expect("temp dir");
+        write_manifest(dir.path(), "alpha", "1.0.0");
+        let mut cfg = config_for(&dir);
+        let mut reg = PluginRegistry::new();
+        reg.reconfigure(load(&cfg));
+        assert_eq!(reg.settings("alpha"), Some(&PluginSettings::default()));
+
+        cfg.entries.insert(
+            "alpha".into(),
+            PluginEntryConfig {
+                timeout_ms: Some(250),
+                ..PluginEntryConfig::default()
+            },
+        );
+        let summary = reg.reconfigure(load(&cfg));
+        assert_eq!(summary.updated, vec!["alpha"]);
+        assert_eq!(
+            reg.settings("alpha")
+                .and_then(|settings| settings.timeout_ms),
+            Some(250)
+        );
+    }
+
+    #[test]
+    fn swap_module_redirects_routing_and_repins() {
+        let mut reg = PluginRegistry::default();
+        reg.register(manifest_with("demo", "demo_tool", "demo"));
+        reg.set_settings(
+            "demo",
+            PluginSettings {
+                sha256: Some("a".repeat(64)),
+                ..PluginSettings::default()
+            },
+        );
+
+        let previous = reg
+            .swap_module("demo", "plugins/demo-v2.wasm", Some("B".repeat(64)))
+            .expect("swap");
+        assert_eq!(previous, "plugins/demo.wasm");
+        assert_eq!(
+            reg.tool_module_path("demo_tool"),
+            Some("plugins/demo-v2.wasm")
+        );
+        assert_eq!(
+            reg.module_sha256("plugins/demo-v2.wasm"),
+            Some("b".repeat(64).as_str())
+        );
+        assert_eq!(reg.module_sha256("plugins/demo.wasm"), None);
+        assert_eq!(reg.generation(), 1);
+
+        assert!(reg.swap_module("missing", "plugins/x.wasm", None).is_err());
+        assert_eq!(reg.generation(), 1);
+    }
+
+    #[test]
+    fn plugins_sharing_a_module_keep_their_own_routing_and_settings() {
+        let mut reg = PluginRegistry::default();
+        for id in ["zeta", "alpha", "mid"] {
+            reg.register(manifest_with(id, &format!("{id}_tool"), id));
+            reg.set_settings(
+                id,
+                PluginSettings {
+                    max_concurrent_calls: Some(id.len()),
+                    ..PluginSettings::default()
+                },
+            );
+        }
+
+        for id in ["zeta", "alpha", "mid"] {
+            assert_eq!(reg.tool_plugin_id(&format!("{id}_tool")), Some(id));
+            assert_eq!(reg.provider_plugin_id(id), Some(id));
+            assert_eq!(
+                reg.tool_module_path(&format!("{id}_tool")),
+                Some("plugins/demo.wasm")
+            );
+            assert_eq!(
+                reg.settings(id)
+                    .and_then(|settings| settings.max_concurrent_calls),
+                Some(id.len())
+            );
+        }
+    }
+
+    #[test]
+    fn quarantine_drops_routing_until_released_or_expired() {
+        let dir = TempDir::new().expect("temp dir");
+        write_manifest(dir.path(), "alpha", "1.0.0");
+        write_manifest(dir.path(), "beta", "1.0.0");
+        let mut reg = PluginRegistry::default();
+        reg.reconfigure(load(&config_for(&dir)));
+
+        let now = Instant::now();
+        assert!(reg.quarantine("alpha", None));
+        assert!(reg.quarantine("beta", Some(now + Duration::from_secs(60))));
+        assert!(!reg.quarantine("alpha", None));
+        assert!(!reg.quarantine("missing", None));
+        assert_eq!(reg.tool_plugin_id("alpha_tool"), None);
+        assert!(reg.tools().is_empty());
+        assert!(reg.manifest("alpha").is_some());
+
+        // Quarantines outlive a reload that keeps the plugin.
+        write_manifest(dir.path(), "alpha", "1.1.0");
+        reg.reconfigure(load(&config_for(&dir)));
+        assert!(reg.is_quarantined("alpha"));
+        assert_eq!(reg.tool_plugin_id("alpha_tool"), None);
+
+        assert!(!reg.has_expired_quarantine(now));
+        assert_eq!(
+            reg.release_expired(now + Duration::from_secs(61)),
+            vec!["beta".to_string()]
+        );
+        assert_eq!(reg.tool_plugin_id("beta_tool"), Some("beta"));
+        assert!(reg.is_quarantined("alpha"));
+
+        assert!(reg.release("alpha"));
+        assert!(!reg.release("alpha"));
+        assert_eq!(reg.tool_plugin_id("alpha_tool"), Some("alpha"));
+    }
 }
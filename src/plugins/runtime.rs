@@ -1,32 +1,142 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::collections::HashMap;
-use std::path::Path;
-use std::sync::{Arc, OnceLock, RwLock};
-use std::time::SystemTime;
-use tokio::sync::Semaphore;
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicI64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock, RwLock, Weak};
+use std::time::{Instant, SystemTime};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
 use tokio::time::{timeout, Duration};
-use wasmtime::{Engine, Extern, Instance, Memory, Module, Store, TypedFunc};
+use wasmtime::{
+    AsContext, Caller, Config as WasmtimeConfig, Engine, Extern, ExternType, Instance, InstancePre,
+    Linker, Memory, Module, Store, StoreLimits, StoreLimitsBuilder, Trap, TypedFunc,
+};
 
+use super::loader::resolve_enable;
 use super::manifest::PluginManifest;
-use super::registry::PluginRegistry;
-use crate::config::PluginsConfig;
+use super::registry::{
+    DiagnosticLevel, PluginDiagnostic, PluginRegistry, PluginSettings, ReconfigureSummary,
+};
+use super::traits::PluginCapability;
+use crate::config::{PluginEntryConfig, PluginsConfig};
+use crate::observability::traits::ObserverMetric;
+use crate::observability::Observer;
+use crate::security::{AuditEvent, AuditEventType, AuditLogger};
 use crate::tools::ToolResult;
 
 const ABI_TOOL_EXEC_FN: &str = "zeroclaw_tool_execute";
 const ABI_PROVIDER_CHAT_FN: &str = "zeroclaw_provider_chat";
+const ABI_MEMORY_CALL_FN: &str = "zeroclaw_memory_call";
 const ABI_ALLOC_FN: &str = "alloc";
 const ABI_DEALLOC_FN: &str = "dealloc";
+const ABI_MIGRATE_FN: &str = "zeroclaw_plugin_migrate";
+const DEFAULT_RETRY_BACKOFF_MS: u64 = 100;
+const DEFAULT_QUARANTINE_WINDOW_SECS: u64 = 300;
+const WASM_MAGIC: &[u8; 4] = b"\0asm";
+const GZIP_MAGIC: &[u8; 2] = b"\x1f\x8b";
+const ZSTD_MAGIC: &[u8; 4] = b"\x28\xb5\x2f\xfd";
+/// Upper bound on a decompressed module, guarding against decompression bombs.
+const MAX_DECOMPRESSED_MODULE_BYTES: u64 = 256 * 1024 * 1024;
 const MAX_WASM_PAYLOAD_BYTES_FALLBACK: usize = 4 * 1024 * 1024;
+/// Resolution of the shared epoch ticker that enforces call deadlines.
+const EPOCH_TICK_MS: u64 = 10;
+const HOST_IMPORT_MODULE: &str = "env";
+const HOST_LOG_FN: &str = "host_log";
+/// Longest message `host_log` forwards; the rest is dropped.
+const MAX_HOST_LOG_BYTES: usize = 4 * 1024;
+const HOST_STATE_GET_FN: &str = "zeroclaw_state_get";
+const HOST_STATE_SET_FN: &str = "zeroclaw_state_set";
+/// Largest serialized state file a single plugin may keep.
+const MAX_PLUGIN_STATE_BYTES: usize = 64 * 1024;
+const HOST_NOW_MILLIS_FN: &str = "zeroclaw_now_millis";
+const HOST_NEW_UUID_FN: &str = "zeroclaw_new_uuid";
 type WasmAbiModule = (
-    Store<()>,
+    Store<PluginStoreState>,
     Instance,
     Memory,
     TypedFunc<i32, i32>,
     TypedFunc<(i32, i32), ()>,
 );
 
+/// Why a plugin call failed. Attached to the `anyhow::Error` returned by plugin
+/// calls; recover it with `err.downcast_ref::<PluginCallError>()`.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PluginCallError {
+    #[error("plugin call timed out after {timeout_ms}ms")]
+    Timeout { timeout_ms: u64 },
+
+    #[error("wasm function '{function}' failed")]
+    Trap { function: String },
+
+    #[error("wasm module '{module}' missing '{export}'")]
+    MissingExport { module: String, export: String },
+
+    #[error("plugin payload rejected: {0}")]
+    Serialization(String),
+
+    #[error("failed to read wasm module {module}")]
+    Io { module: String },
+
+    #[error("failed to load wasm module {module}")]
+    InvalidModule { module: String },
+
+    #[error("failed to instantiate wasm module {module}")]
+    Instantiation { module: String },
+
+    #[error("wasm module {module} does not match its pinned sha256")]
+    IntegrityMismatch { module: String },
+
+    #[error("failed to decompress wasm module {module}")]
+    Decompression { module: String },
+}
+
+impl PluginCallError {
+    /// Whether retrying the same call might succeed: a timeout, or a module
+    /// file that could not be read (e.g. while it is being replaced). Invalid
+    /// modules, traps and bad payloads fail the same way every time.
+    pub fn is_transient(&self) -> bool {
+        matches!(self, Self::Timeout { .. } | Self::Io { .. })
+    }
+}
+
+/// How often a plugin's transiently failing calls are retried.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt.
+    pub attempts: u32,
+    /// Delay before the first retry; doubled for each further retry.
+    pub backoff_ms: u64,
+    /// Whether timeouts are retried as well as unreadable modules. A guest
+    /// interrupted by its timeout may already have had side effects, so
+    /// this is only set for plugins whose manifest declares them idempotent.
+    pub retry_timeouts: bool,
+}
+
+/// When repeated timeouts take a plugin out of routing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuarantinePolicy {
+    /// Timed-out calls within `window` that trigger the quarantine.
+    pub max_timeouts: u32,
+    /// Rolling window the timeouts are counted over.
+    pub window: Duration,
+    /// How long the quarantine lasts. `None` keeps the plugin out until it
+    /// is re-enabled explicitly.
+    pub cooldown: Option<Duration>,
+}
+
+/// Retry and quarantine policies for one plugin's calls. Derived from the
+/// live config on each call rather than stored in the registry.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct CallPolicy {
+    /// `None` disables retries.
+    retry: Option<RetryPolicy>,
+    /// `None` never quarantines.
+    quarantine: Option<QuarantinePolicy>,
+}
+
 #[derive(Debug, Default)]
 pub struct PluginRuntime;
 
@@ -47,15 +157,21 @@ impl PluginRuntime {
         if !config.enabled {
             return Ok(registry);
         }
+        let mut skipped_over_limit = 0usize;
         for dir in &config.load_paths {
             let path = Path::new(dir);
             if !path.exists() {
                 continue;
             }
-            let entries = std::fs::read_dir(path)
-                .with_context(|| format!("failed to read plugin directory {}", path.display()))?;
-            for entry in entries.flatten() {
-                let path = entry.path();
+            // read_dir order is filesystem-specific; sort so the same manifests
+            // survive the max_plugins cap everywhere.
+            let mut manifest_paths: Vec<_> = std::fs::read_dir(path)
+                .with_context(|| format!("failed to read plugin directory {}", path.display()))?
+                .flatten()
+                .map(|entry| entry.path())
+                .collect();
+            manifest_paths.sort();
+            for path in manifest_paths {
                 if !path.is_file() {
                     continue;
                 }
@@ -79,11 +195,60 @@ impl PluginRuntime {
                     })?
                 };
                 let manifest = self.load_manifest(manifest)?;
+                if let Err(reason) = resolve_enable(&manifest.id, config) {
+                    tracing::debug!(plugin = %manifest.id, reason = %reason, "plugin disabled");
+                    registry.register_disabled(manifest);
+                    continue;
+                }
+                if registry.len() >= config.max_plugins
+                    && !registry.all_manifests().iter().any(|m| m.id == manifest.id)
+                {
+                    skipped_over_limit += 1;
+                    continue;
+                }
+                let entry = config.entries.get(&manifest.id);
+                let settings = match admit_plugin(&manifest, entry) {
+                    Ok(settings) => settings,
+                    Err(reason) => {
+                        refuse_plugin(&mut registry, &manifest, &path, &reason);
+                        continue;
+                    }
+                };
+                if let Some(entry) = entry {
+                    registry.set_plugin_config(&manifest.id, entry.config.clone());
+                }
+                registry.set_settings(&manifest.id, settings);
                 registry.register(manifest);
             }
         }
+        if skipped_over_limit > 0 {
+            tracing::warn!(
+                max_plugins = config.max_plugins,
+                dropped = skipped_over_limit,
+                "plugin limit reached; skipping excess plugin manifests"
+            );
+        }
         Ok(registry)
     }
+
+    /// Check every discovered plugin in the live registry concurrently,
+    /// sorted by id. Enabled plugins' modules must compile, link against the
+    /// host imports, export the ABI their manifest needs and fit their initial
+    /// memory in the configured limit; disabled plugins are listed without a
+    /// check. Nothing is instantiated, so no guest code runs, and checks take
+    /// no call permits, so a plugin busy with calls still reports healthy.
+    /// Each check is bounded by the plugin's call timeout.
+    pub async fn health_report(&self) -> Vec<PluginHealth> {
+        check_registry_health(&current_registry(), current_limits()).await
+    }
+
+    /// Dry-run check of every plugin in the live registry, sorted by id: the
+    /// module file exists, starts with the WASM magic, compiles, and exports
+    /// the ABI its manifest needs. Nothing is instantiated, so no guest code
+    /// runs.
+    pub fn validate_all(&self) -> Vec<PluginValidation> {
+        validate_registry(&current_registry())
+    }
 }
 
 #[derive(Debug, Serialize)]
@@ -103,28 +268,756 @@ struct ProviderPluginResponse {
     error: Option<String>,
 }
 
-fn instantiate_module(module_path: &str) -> Result<WasmAbiModule> {
-    let engine = Engine::default();
-    let module = Module::from_file(&engine, module_path)
-        .with_context(|| format!("failed to load wasm module {module_path}"))?;
-    let mut store = Store::new(&engine, ());
-    let instance = Instance::new(&mut store, &module, &[])
-        .with_context(|| format!("failed to instantiate wasm module {module_path}"))?;
+/// Refuse a plugin whose module bytes don't hash to the pinned `expected` digest.
+fn verify_module_sha256(plugin_id: &str, module_path: &str, expected: &str) -> Result<()> {
+    let bytes = std::fs::read(module_path).with_context(|| {
+        format!("failed to read module {module_path} for plugin '{plugin_id}' checksum")
+    })?;
+    let actual = hex::encode(Sha256::digest(&bytes));
+    if !actual.eq_ignore_ascii_case(expected.trim()) {
+        anyhow::bail!(
+            "plugin '{plugin_id}' module {module_path} sha256 mismatch: expected {}, got {actual}",
+            expected.trim()
+        );
+    }
+    Ok(())
+}
+
+/// Refuse a plugin that declares a capability missing from its entry's
+/// `allowed_capabilities`. Names match case-insensitively.
+fn check_allowed_capabilities(manifest: &PluginManifest, allowed: &[String]) -> Result<()> {
+    for capability in &manifest.capabilities {
+        let name = serde_json::to_value(capability)?
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+        if !allowed
+            .iter()
+            .any(|entry| entry.trim().eq_ignore_ascii_case(&name))
+        {
+            anyhow::bail!(
+                "plugin '{}' declares capability '{name}' which is not in allowed_capabilities",
+                manifest.id
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Apply the checks `entry` imposes on `manifest` and return the runtime
+/// settings it configures. An error refuses this plugin only. The config
+/// table is checked against the manifest's schema only when the plugin has an
+/// entry; without one the plugin runs on its own defaults.
+fn admit_plugin(
+    manifest: &PluginManifest,
+    entry: Option<&PluginEntryConfig>,
+) -> Result<PluginSettings> {
+    manifest.check_config_schema()?;
+    let Some(entry) = entry else {
+        return Ok(PluginSettings::default());
+    };
+    let empty_config = Value::Object(serde_json::Map::new());
+    let plugin_config = if entry.config.is_null() {
+        &empty_config
+    } else {
+        &entry.config
+    };
+    manifest.validate_config(plugin_config)?;
+    let sha256 = entry.sha256.as_deref().map(str::trim);
+    if let Some(expected) = sha256 {
+        verify_module_sha256(&manifest.id, &manifest.module_path, expected)?;
+    }
+    if let Some(allowed) = &entry.allowed_capabilities {
+        check_allowed_capabilities(manifest, allowed)?;
+    }
+    if entry.max_concurrent_calls == Some(0) {
+        anyhow::bail!(
+            "plugin '{}' max_concurrent_calls must be greater than 0",
+            manifest.id
+        );
+    }
+    if entry.quarantine_after_timeouts == Some(0) {
+        anyhow::bail!(
+            "plugin '{}' quarantine_after_timeouts must be greater than 0",
+            manifest.id
+        );
+    }
+    if entry.retry_attempts.is_some_and(|attempts| attempts > 0) && !manifest.idempotent {
+        // A timed-out guest may already have had side effects; running it
+        // again is only safe when the plugin says so.
+        tracing::warn!(
+            plugin = %manifest.id,
+            "timed-out calls won't be retried: manifest does not declare idempotent = true"
+        );
+    }
+    Ok(PluginSettings {
+        max_concurrent_calls: entry.max_concurrent_calls,
+        timeout_ms: entry.timeout_ms.map(|timeout_ms| timeout_ms.max(1)),
+        sha256: sha256.map(str::to_ascii_lowercase),
+        read_fallback: entry.read_fallback,
+        allowed_categories: entry.allowed_categories.clone(),
+    })
+}
+
+/// Retry and quarantine policies `entry` configures for `manifest`'s calls.
+fn call_policy_for(manifest: &PluginManifest, entry: Option<&PluginEntryConfig>) -> CallPolicy {
+    let Some(entry) = entry else {
+        return CallPolicy::default();
+    };
+    CallPolicy {
+        retry: entry
+            .retry_attempts
+            .filter(|attempts| *attempts > 0)
+            .map(|attempts| RetryPolicy {
+                attempts,
+                backoff_ms: entry.retry_backoff_ms.unwrap_or(DEFAULT_RETRY_BACKOFF_MS),
+                retry_timeouts: manifest.idempotent,
+            }),
+        quarantine: entry
+            .quarantine_after_timeouts
+            .filter(|max_timeouts| *max_timeouts > 0)
+            .map(|max_timeouts| QuarantinePolicy {
+                max_timeouts,
+                window: Duration::from_secs(
+                    entry
+                        .quarantine_window_secs
+                        .unwrap_or(DEFAULT_QUARANTINE_WINDOW_SECS),
+                ),
+                cooldown: entry.quarantine_cooldown_secs.map(Duration::from_secs),
+            }),
+    }
+}
+
+/// Call policy for registered plugin `plugin_id` under the live config.
+fn call_policy(registry: &PluginRegistry, plugin_id: &str) -> CallPolicy {
+    let Some(manifest) = registry.manifest(plugin_id) else {
+        return CallPolicy::default();
+    };
+    let guard = registry_cell()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let entry = guard
+        .config
+        .as_ref()
+        .and_then(|config| config.entries.get(plugin_id));
+    call_policy_for(manifest, entry)
+}
+
+/// Leave `manifest` out of the registry, recording why as a diagnostic so the
+/// remaining plugins still load.
+fn refuse_plugin(
+    registry: &mut PluginRegistry,
+    manifest: &PluginManifest,
+    manifest_path: &Path,
+    reason: &anyhow::Error,
+) {
+    let message = format!("{reason:#}");
+    tracing::warn!(plugin = %manifest.id, reason = %message, "plugin refused");
+    registry.push_diagnostic(PluginDiagnostic {
+        level: DiagnosticLevel::Error,
+        plugin_id: Some(manifest.id.clone()),
+        source: Some(manifest_path.display().to_string()),
+        message,
+    });
+}
+
+/// Engine shared by every plugin call. A background thread advances its epoch
+/// every [`EPOCH_TICK_MS`], and each store sets its own deadline in ticks.
+///
+/// The engine lives in a static and is never dropped, so the ticker thread
+/// is started once and runs for the rest of the process.
+fn shared_engine() -> Result<&'static Engine> {
+    static ENGINE: OnceLock<Engine> = OnceLock::new();
+    if let Some(engine) = ENGINE.get() {
+        return Ok(engine);
+    }
+    let mut wasm_config = WasmtimeConfig::new();
+    wasm_config.epoch_interruption(true);
+    let engine = Engine::new(&wasm_config).context("failed to create wasm engine")?;
+    Ok(ENGINE.get_or_init(|| {
+        let ticker = engine.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(std::time::Duration::from_millis(EPOCH_TICK_MS));
+            ticker.increment_epoch();
+        });
+        engine
+    }))
+}
+
+/// Linker holding every host import, built once for the shared engine.
+fn shared_linker() -> Result<&'static Linker<PluginStoreState>> {
+    static LINKER: OnceLock<Linker<PluginStoreState>> = OnceLock::new();
+    if let Some(linker) = LINKER.get() {
+        return Ok(linker);
+    }
+    let mut linker = Linker::new(shared_engine()?);
+    linker
+        .func_wrap(HOST_IMPORT_MODULE, HOST_LOG_FN, host_log)
+        .and_then(|linker| linker.func_wrap(HOST_IMPORT_MODULE, HOST_STATE_GET_FN, host_state_get))
+        .and_then(|linker| linker.func_wrap(HOST_IMPORT_MODULE, HOST_STATE_SET_FN, host_state_set))
+        .and_then(|linker| {
+            linker.func_wrap(HOST_IMPORT_MODULE, HOST_NOW_MILLIS_FN, host_now_millis)
+        })
+        .and_then(|linker| linker.func_wrap(HOST_IMPORT_MODULE, HOST_NEW_UUID_FN, host_new_uuid))
+        .context("failed to register plugin host imports")?;
+    Ok(LINKER.get_or_init(|| linker))
+}
+
+struct CachedModule {
+    modified: Option<SystemTime>,
+    /// Hex SHA-256 of the file the module was compiled from.
+    sha256: String,
+    module: Module,
+    /// `module` with its imports resolved against [`shared_linker`], filled
+    /// in by the first call.
+    pre: Option<InstancePre<PluginStoreState>>,
+}
+
+fn module_cache() -> &'static Mutex<HashMap<String, CachedModule>> {
+    static CELL: OnceLock<Mutex<HashMap<String, CachedModule>>> = OnceLock::new();
+    CELL.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn read_module_file(module_path: &str) -> Result<Vec<u8>> {
+    std::fs::read(module_path).with_context(|| PluginCallError::Io {
+        module: module_path.to_string(),
+    })
+}
+
+/// Raw WASM bytes of `module_path`, transparently decompressing gzip and
+/// (with the `plugins-zstd` feature) zstd files, detected by magic bytes.
+fn read_module_bytes(module_path: &str) -> Result<Vec<u8>> {
+    decompress_module(module_path, read_module_file(module_path)?)
+}
+
+fn decompress_module(module_path: &str, raw: Vec<u8>) -> Result<Vec<u8>> {
+    use std::io::Read;
+
+    let decoder: Box<dyn Read + '_> = if raw.starts_with(GZIP_MAGIC) {
+        Box::new(flate2::read::GzDecoder::new(raw.as_slice()))
+    } else if raw.starts_with(ZSTD_MAGIC) {
+        zstd_decoder(module_path, &raw)?
+    } else {
+        return Ok(raw);
+    };
+
+    let mut bytes = Vec::new();
+    decoder
+        .take(MAX_DECOMPRESSED_MODULE_BYTES + 1)
+        .read_to_end(&mut bytes)
+        .with_context(|| decompression_error(module_path))?;
+    if u64::try_from(bytes.len()).unwrap_or(u64::MAX) > MAX_DECOMPRESSED_MODULE_BYTES {
+        return Err(anyhow::anyhow!(
+            "decompressed module exceeds {MAX_DECOMPRESSED_MODULE_BYTES} bytes"
+        )
+        .context(decompression_error(module_path)));
+    }
+    Ok(bytes)
+}
+
+fn decompression_error(module_path: &str) -> PluginCallError {
+    PluginCallError::Decompression {
+        module: module_path.to_string(),
+    }
+}
+
+#[cfg(feature = "plugins-zstd")]
+fn zstd_decoder<'a>(module_path: &str, raw: &'a [u8]) -> Result<Box<dyn std::io::Read + 'a>> {
+    let decoder =
+        zstd::stream::read::Decoder::new(raw).with_context(|| decompression_error(module_path))?;
+    Ok(Box::new(decoder))
+}
+
+#[cfg(not(feature = "plugins-zstd"))]
+fn zstd_decoder<'a>(module_path: &str, _raw: &'a [u8]) -> Result<Box<dyn std::io::Read + 'a>> {
+    Err(
+        anyhow::anyhow!("zstd-compressed modules require the plugins-zstd feature")
+            .context(decompression_error(module_path)),
+    )
+}
+
+/// Digest the registry pins for `module_path`, if any.
+fn pinned_module_sha256(module_path: &str) -> Option<String> {
+    let guard = registry_cell()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    guard
+        .registry
+        .module_sha256(module_path)
+        .map(str::to_string)
+}
+
+/// Compiled module for `module_path`, compiling on first use and again
+/// whenever the file's mtime changes.
+///
+/// A pinned module is hashed from the exact bytes about to be compiled. If a
+/// file swapped after the registry was loaded no longer matches its pin, the
+/// previously verified module keeps serving calls; with none to fall back
+/// on, the call is refused rather than run.
+fn cached_module(engine: &Engine, module_path: &str) -> Result<Module> {
+    let pinned = pinned_module_sha256(module_path);
+    let modified = std::fs::metadata(module_path)
+        .with_context(|| PluginCallError::Io {
+            module: module_path.to_string(),
+        })?
+        .modified()
+        .ok();
+    {
+        let guard = module_cache()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(cached) = guard.get(module_path) {
+            let pin_matches = pinned
+                .as_deref()
+                .is_none_or(|expected| expected.eq_ignore_ascii_case(&cached.sha256));
+            if cached.modified == modified && pin_matches {
+                return Ok(cached.module.clone());
+            }
+        }
+    }
+    match compile_module(engine, module_path, pinned.as_deref(), modified) {
+        Err(err) if is_integrity_mismatch(&err) => {
+            retain_verified_module(module_path, pinned.as_deref(), modified).ok_or(err)
+        }
+        result => result,
+    }
+}
+
+fn is_integrity_mismatch(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<PluginCallError>(),
+        Some(PluginCallError::IntegrityMismatch { .. })
+    )
+}
+
+/// The cached module for `module_path` if it still matches `pinned`, marked
+/// current for `modified` so the rejected file isn't re-hashed on every call.
+fn retain_verified_module(
+    module_path: &str,
+    pinned: Option<&str>,
+    modified: Option<SystemTime>,
+) -> Option<Module> {
+    let mut guard = module_cache()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    let cached = guard
+        .get_mut(module_path)
+        .filter(|cached| pinned.is_some_and(|pin| pin.eq_ignore_ascii_case(&cached.sha256)))?;
+    tracing::warn!(
+        module = %module_path,
+        "plugin module on disk no longer matches its pinned sha256; keeping the verified module"
+    );
+    cached.modified = modified;
+    Some(cached.module.clone())
+}
+
+/// Read, verify and compile `module_path`, replacing its cache entry only on
+/// success. Runs outside the cache lock so a slow module doesn't block other
+/// plugins. Only the compiled module is kept.
+fn compile_module(
+    engine: &Engine,
+    module_path: &str,
+    pinned: Option<&str>,
+    modified: Option<SystemTime>,
+) -> Result<Module> {
+    let raw = read_module_file(module_path)?;
+    let sha256 = hex::encode(Sha256::digest(&raw));
+    if let Some(expected) = pinned {
+        if !expected.eq_ignore_ascii_case(&sha256) {
+            tracing::warn!(
+                module = %module_path,
+                expected = %expected,
+                actual = %sha256,
+                "refusing plugin module that does not match its pinned sha256"
+            );
+            return Err(PluginCallError::IntegrityMismatch {
+                module: module_path.to_string(),
+            }
+            .into());
+        }
+    }
+    let bytes = decompress_module(module_path, raw)?;
+    let module = Module::new(engine, &bytes).with_context(|| PluginCallError::InvalidModule {
+        module: module_path.to_string(),
+    })?;
+    module_cache()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .insert(
+            module_path.to_string(),
+            CachedModule {
+                modified,
+                sha256,
+                module: module.clone(),
+                pre: None,
+            },
+        );
+    Ok(module)
+}
+
+/// Compile every registered plugin module ahead of the first call.
+///
+/// Returns how many modules were compiled; modules that fail to compile are
+/// logged and left for the first call to report.
+pub fn precompile_modules() -> Result<usize> {
+    let engine = shared_engine()?;
+    let registry = current_registry();
+    let mut module_paths: Vec<&str> = registry
+        .all_manifests()
+        .into_iter()
+        .map(|manifest| manifest.module_path.as_str())
+        .collect();
+    module_paths.sort_unstable();
+    module_paths.dedup();
+
+    let mut compiled = 0;
+    for module_path in module_paths {
+        match cached_module(engine, module_path) {
+            Ok(_) => compiled += 1,
+            Err(err) => {
+                tracing::warn!(
+                    module = %module_path,
+                    error = %format!("{err:#}"),
+                    "plugin module precompile failed"
+                );
+            }
+        }
+    }
+    Ok(compiled)
+}
+
+/// Per-call store data: the memory limiter plus the plugin id host imports
+/// attach to what they emit.
+struct PluginStoreState {
+    plugin_id: String,
+    /// Backing file for `zeroclaw_state_*`; `None` when the plugin id can't
+    /// name a file, in which case state calls fail.
+    state_path: Option<PathBuf>,
+    limits: StoreLimits,
+}
+
+/// `<plugins.state_dir>/<plugin_id>.json`, for ids made only of ASCII
+/// letters, digits, `-`, `_` and `.` (not leading), so one plugin can never
+/// address another's file.
+fn plugin_state_path(plugin_id: &str) -> Option<PathBuf> {
+    let usable = !plugin_id.is_empty()
+        && !plugin_id.starts_with('.')
+        && plugin_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+    if !usable {
+        return None;
+    }
+    let state_dir = registry_cell()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .config
+        .as_ref()
+        .map_or_else(
+            || PluginsConfig::default().state_dir,
+            |config| config.state_dir.clone(),
+        );
+    Some(PathBuf::from(shellexpand::tilde(&state_dir).as_ref()).join(format!("{plugin_id}.json")))
+}
+
+/// Lock serializing read-modify-write of plugin `plugin_id`'s state file
+/// across its concurrent calls; other plugins' state calls never wait on it.
+fn plugin_state_lock(plugin_id: &str) -> Arc<Mutex<()>> {
+    static LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+    let mut locks = LOCKS
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    Arc::clone(locks.entry(plugin_id.to_string()).or_default())
+}
+
+thread_local! {
+    /// Plugins whose state lock this thread holds.
+    static HELD_STATE_LOCKS: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+/// Run `f` under plugin `plugin_id`'s state lock. Reentrant on one thread: a
+/// module swap holds the lock across its migrate call, whose state imports
+/// run on the same thread, so other calls' state writes wait for the switch.
+fn with_plugin_state_lock<T>(plugin_id: &str, f: impl FnOnce() -> T) -> T {
+    struct Held<'a>(&'a str);
+    impl Drop for Held<'_> {
+        fn drop(&mut self) {
+            HELD_STATE_LOCKS.with(|held| held.borrow_mut().remove(self.0));
+        }
+    }
+
+    if HELD_STATE_LOCKS.with(|held| held.borrow().contains(plugin_id)) {
+        return f();
+    }
+    let lock = plugin_state_lock(plugin_id);
+    let _guard = lock
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    HELD_STATE_LOCKS.with(|held| held.borrow_mut().insert(plugin_id.to_string()));
+    let _held = Held(plugin_id);
+    f()
+}
+
+fn load_plugin_state(path: &Path) -> Result<BTreeMap<String, String>> {
+    match std::fs::read(path) {
+        Ok(bytes) => serde_json::from_slice(&bytes)
+            .with_context(|| format!("corrupt plugin state file {}", path.display())),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(BTreeMap::new()),
+        Err(err) => Err(err).with_context(|| format!("failed to read {}", path.display())),
+    }
+}
+
+/// Persist `state` via a uniquely named sibling temp file and rename,
+/// refusing to grow it past [`MAX_PLUGIN_STATE_BYTES`].
+fn save_plugin_state(path: &Path, state: &BTreeMap<String, String>) -> Result<()> {
+    use std::io::Write;
+
+    let bytes = serde_json::to_vec(state)?;
+    if bytes.len() > MAX_PLUGIN_STATE_BYTES {
+        anyhow::bail!("plugin state would exceed {MAX_PLUGIN_STATE_BYTES} bytes");
+    }
+    let dir = path.parent().unwrap_or_else(|| Path::new("."));
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+    let mut tmp = tempfile::NamedTempFile::new_in(dir)
+        .with_context(|| format!("failed to create a temp file in {}", dir.display()))?;
+    tmp.write_all(&bytes)
+        .with_context(|| format!("failed to write {}", tmp.path().display()))?;
+    tmp.persist(path)
+        .with_context(|| format!("failed to replace {}", path.display()))?;
+    Ok(())
+}
+
+/// UTF-8 string at `ptr..ptr + len` in the caller's memory, capped at the
+/// state size bound.
+fn guest_state_str(
+    caller: &mut Caller<'_, PluginStoreState>,
+    ptr: i32,
+    len: i32,
+) -> Result<String> {
+    if usize::try_from(len).unwrap_or(usize::MAX) > MAX_PLUGIN_STATE_BYTES {
+        anyhow::bail!("state key or value exceeds {MAX_PLUGIN_STATE_BYTES} bytes");
+    }
+    let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+        anyhow::bail!("plugin exports no memory");
+    };
+    let bytes = read_guest_bytes(&*caller, &memory, ptr, len)?;
+    String::from_utf8(bytes).context("state key or value is not UTF-8")
+}
+
+/// Copy `bytes` into a buffer from the guest's `alloc` export and return it
+/// packed as `ptr << 32 | len`; the guest frees it with `dealloc`.
+fn copy_to_guest(caller: &mut Caller<'_, PluginStoreState>, bytes: &[u8]) -> Result<i64> {
+    let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+        anyhow::bail!("plugin exports no memory");
+    };
+    let alloc = caller
+        .get_export(ABI_ALLOC_FN)
+        .and_then(Extern::into_func)
+        .context("plugin exports no alloc")?
+        .typed::<i32, i32>(&*caller)?;
+    let len = i32::try_from(bytes.len()).context("value too large for wasm ABI")?;
+    let ptr = alloc.call(&mut *caller, len)?;
+    memory
+        .write(
+            &mut *caller,
+            usize::try_from(ptr).context("alloc returned a negative pointer")?,
+            bytes,
+        )
+        .context("alloc returned an out-of-bounds buffer")?;
+    pack_ptr_len(ptr, len)
+}
+
+fn state_get(caller: &mut Caller<'_, PluginStoreState>, key_ptr: i32, key_len: i32) -> Result<i64> {
+    let key = guest_state_str(caller, key_ptr, key_len)?;
+    let path = caller
+        .data()
+        .state_path
+        .clone()
+        .context("plugin id cannot hold state")?;
+    let value = with_plugin_state_lock(&caller.data().plugin_id, || {
+        load_plugin_state(&path).map(|mut state| state.remove(&key))
+    })?;
+    match value {
+        Some(value) => copy_to_guest(caller, value.as_bytes()),
+        None => Ok(-1),
+    }
+}
+
+fn state_set(
+    caller: &mut Caller<'_, PluginStoreState>,
+    key_ptr: i32,
+    key_len: i32,
+    value_ptr: i32,
+    value_len: i32,
+) -> Result<()> {
+    let key = guest_state_str(caller, key_ptr, key_len)?;
+    let value = guest_state_str(caller, value_ptr, value_len)?;
+    let path = caller
+        .data()
+        .state_path
+        .as_deref()
+        .context("plugin id cannot hold state")?;
+    with_plugin_state_lock(&caller.data().plugin_id, || {
+        let mut state = load_plugin_state(path)?;
+        state.insert(key, value);
+        save_plugin_state(path, &state)
+    })
+}
+
+/// `env.zeroclaw_state_get(key_ptr, key_len) -> i64`: the value the calling
+/// plugin stored under a UTF-8 key, in a guest buffer packed as
+/// `ptr << 32 | len`, or `-1` when the key is unset or state is unavailable.
+fn host_state_get(mut caller: Caller<'_, PluginStoreState>, key_ptr: i32, key_len: i32) -> i64 {
+    state_get(&mut caller, key_ptr, key_len).unwrap_or_else(|err| {
+        tracing::warn!(plugin_id = %caller.data().plugin_id, "plugin state get failed: {err:#}");
+        -1
+    })
+}
+
+/// `env.zeroclaw_state_set(key_ptr, key_len, value_ptr, value_len) -> i32`:
+/// store a UTF-8 value for the calling plugin. Returns `0` on success and
+/// `-1` when the value is rejected (not UTF-8, over the size bound) or can't
+/// be persisted.
+fn host_state_set(
+    mut caller: Caller<'_, PluginStoreState>,
+    key_ptr: i32,
+    key_len: i32,
+    value_ptr: i32,
+    value_len: i32,
+) -> i32 {
+    match state_set(&mut caller, key_ptr, key_len, value_ptr, value_len) {
+        Ok(()) => 0,
+        Err(err) => {
+            tracing::warn!(plugin_id = %caller.data().plugin_id, "plugin state set failed: {err:#}");
+            -1
+        }
+    }
+}
+
+/// `env.host_log(level, ptr, len)`: forward a UTF-8 message from guest memory
+/// to `tracing`. Levels map 0=trace, 1=debug, 2=info, 3=warn, 4=error.
+fn host_log(mut caller: Caller<'_, PluginStoreState>, level: i32, ptr: i32, len: i32) {
+    let Some(Extern::Memory(memory)) = caller.get_export("memory") else {
+        return;
+    };
+    let len = len.min(i32::try_from(MAX_HOST_LOG_BYTES).unwrap_or(i32::MAX));
+    let Ok(bytes) = read_guest_bytes(&caller, &memory, ptr, len) else {
+        return;
+    };
+    let message = String::from_utf8_lossy(&bytes);
+    let plugin_id = caller.data().plugin_id.as_str();
+    match level {
+        i32::MIN..=0 => tracing::trace!(plugin_id, "{message}"),
+        1 => tracing::debug!(plugin_id, "{message}"),
+        2 => tracing::info!(plugin_id, "{message}"),
+        3 => tracing::warn!(plugin_id, "{message}"),
+        _ => tracing::error!(plugin_id, "{message}"),
+    }
+}
+
+/// `env.zeroclaw_now_millis() -> i64`: the host's wall clock in Unix
+/// milliseconds, never lower than a value already handed to a plugin.
+fn host_now_millis() -> i64 {
+    static LAST: AtomicI64 = AtomicI64::new(i64::MIN);
+    let now = chrono::Utc::now().timestamp_millis();
+    LAST.fetch_max(now, Ordering::SeqCst).max(now)
+}
+
+/// `env.zeroclaw_new_uuid() -> i64`: a fresh random (v4) UUID in hyphenated
+/// form, in a guest buffer packed as `ptr << 32 | len`, or `-1` if it could
+/// not be copied into guest memory.
+fn host_new_uuid(mut caller: Caller<'_, PluginStoreState>) -> i64 {
+    let id = uuid::Uuid::new_v4().hyphenated().to_string();
+    copy_to_guest(&mut caller, id.as_bytes()).unwrap_or_else(|err| {
+        tracing::warn!(plugin_id = %caller.data().plugin_id, "plugin uuid copy failed: {err:#}");
+        -1
+    })
+}
+
+/// Pre-linked form of `module`, the current compilation of `module_path`,
+/// cached next to it so each call only creates a store and instantiates.
+fn instance_pre(module_path: &str, module: &Module) -> Result<InstancePre<PluginStoreState>> {
+    let is_current = |cached: &CachedModule| cached.module.image_range() == module.image_range();
+    {
+        let guard = module_cache()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if let Some(pre) = guard
+            .get(module_path)
+            .filter(|cached| is_current(cached))
+            .and_then(|cached| cached.pre.clone())
+        {
+            return Ok(pre);
+        }
+    }
+    let pre = shared_linker()?.instantiate_pre(module).with_context(|| {
+        PluginCallError::Instantiation {
+            module: module_path.to_string(),
+        }
+    })?;
+    if let Some(cached) = module_cache()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .get_mut(module_path)
+        .filter(|cached| is_current(cached))
+    {
+        cached.pre = Some(pre.clone());
+    }
+    Ok(pre)
+}
+
+fn instantiate_module(
+    plugin_id: &str,
+    module_path: &str,
+    limits: PluginExecutionLimits,
+) -> Result<WasmAbiModule> {
+    let engine = shared_engine()?;
+    let module = cached_module(engine, module_path)?;
+    let pre = instance_pre(module_path, &module)?;
+    // Cap linear memory at instantiation and on every `memory.grow`; a grow past
+    // the limit traps instead of returning -1 so runaway plugins fail loudly.
+    let store_limits = StoreLimitsBuilder::new()
+        .memory_size(usize::try_from(limits.memory_limit_bytes).unwrap_or(usize::MAX))
+        .trap_on_grow_failure(true)
+        .build();
+    let mut store = Store::new(
+        engine,
+        PluginStoreState {
+            plugin_id: plugin_id.to_string(),
+            state_path: plugin_state_path(plugin_id),
+            limits: store_limits,
+        },
+    );
+    store.limiter(|state| &mut state.limits);
+    store.set_epoch_deadline(limits.invoke_timeout_ms.div_ceil(EPOCH_TICK_MS).max(1));
+    let instance = pre
+        .instantiate(&mut store)
+        .with_context(|| PluginCallError::Instantiation {
+            module: module_path.to_string(),
+        })?;
     let memory = match instance.get_export(&mut store, "memory") {
         Some(Extern::Memory(memory)) => memory,
-        _ => anyhow::bail!("wasm module '{module_path}' missing exported memory"),
+        _ => return Err(missing_export(module_path, "memory").into()),
     };
     let alloc = instance
         .get_typed_func::<i32, i32>(&mut store, ABI_ALLOC_FN)
-        .with_context(|| format!("wasm module '{module_path}' missing '{ABI_ALLOC_FN}'"))?;
+        .with_context(|| missing_export(module_path, ABI_ALLOC_FN))?;
     let dealloc = instance
         .get_typed_func::<(i32, i32), ()>(&mut store, ABI_DEALLOC_FN)
-        .with_context(|| format!("wasm module '{module_path}' missing '{ABI_DEALLOC_FN}'"))?;
+        .with_context(|| missing_export(module_path, ABI_DEALLOC_FN))?;
     Ok((store, instance, memory, alloc, dealloc))
 }
 
+fn missing_export(module_path: &str, export: &str) -> PluginCallError {
+    PluginCallError::MissingExport {
+        module: module_path.to_string(),
+        export: export.to_string(),
+    }
+}
+
 fn write_guest_bytes(
-    store: &mut Store<()>,
+    store: &mut Store<PluginStoreState>,
     memory: &Memory,
     alloc: &TypedFunc<i32, i32>,
     bytes: &[u8],
@@ -140,7 +1033,7 @@ fn write_guest_bytes(
     Ok((ptr, len_i32))
 }
 
-fn read_guest_bytes(store: &mut Store<()>, memory: &Memory, ptr: i32, len: i32) -> Result<Vec<u8>> {
+fn read_guest_bytes(store: impl AsContext, memory: &Memory, ptr: i32, len: i32) -> Result<Vec<u8>> {
     if ptr < 0 || len < 0 {
         anyhow::bail!("wasm ABI returned negative ptr/len");
     }
@@ -149,16 +1042,22 @@ fn read_guest_bytes(store: &mut Store<()>, memory: &Memory, ptr: i32, len: i32)
     let end = ptr_usize
         .checked_add(len_usize)
         .context("overflow in output range")?;
-    if end > memory.data_size(&mut *store) {
+    if end > memory.data_size(&store) {
         anyhow::bail!("output range exceeds wasm memory bounds");
     }
     let mut out = vec![0u8; len_usize];
     memory
-        .read(&mut *store, ptr_usize, &mut out)
+        .read(&store, ptr_usize, &mut out)
         .context("failed to read wasm output bytes")?;
     Ok(out)
 }
 
+fn pack_ptr_len(ptr: i32, len: i32) -> Result<i64> {
+    let ptr = u32::try_from(ptr).context("negative wasm pointer")?;
+    let len = u32::try_from(len).context("negative wasm length")?;
+    i64::try_from((u64::from(ptr) << 32) | u64::from(len)).context("ptr out of i32 range")
+}
+
 fn unpack_ptr_len(packed: i64) -> Result<(i32, i32)> {
     let raw = u64::try_from(packed).context("wasm ABI returned negative packed ptr/len")?;
     let ptr_u32 = (raw >> 32) as u32;
@@ -168,29 +1067,56 @@ fn unpack_ptr_len(packed: i64) -> Result<(i32, i32)> {
     Ok((ptr, len))
 }
 
-fn call_wasm_json(module_path: &str, fn_name: &str, input_json: &str) -> Result<String> {
+fn call_wasm_json(
+    plugin_id: &str,
+    module_path: &str,
+    fn_name: &str,
+    input_json: &str,
+    limits: PluginExecutionLimits,
+) -> Result<String> {
     if input_json.len() > MAX_WASM_PAYLOAD_BYTES_FALLBACK {
-        anyhow::bail!("wasm input payload exceeds safety limit");
+        return Err(PluginCallError::Serialization(
+            "wasm input payload exceeds safety limit".to_string(),
+        )
+        .into());
     }
-    let (mut store, instance, memory, alloc, dealloc) = instantiate_module(module_path)?;
+    let (mut store, instance, memory, alloc, dealloc) =
+        instantiate_module(plugin_id, module_path, limits)?;
     let call = instance
         .get_typed_func::<(i32, i32), i64>(&mut store, fn_name)
-        .with_context(|| format!("wasm module '{module_path}' missing '{fn_name}'"))?;
+        .with_context(|| missing_export(module_path, fn_name))?;
 
     let (in_ptr, in_len) = write_guest_bytes(&mut store, &memory, &alloc, input_json.as_bytes())?;
-    let packed = call
-        .call(&mut store, (in_ptr, in_len))
-        .with_context(|| format!("wasm function '{fn_name}' failed"))?;
+    let packed = call.call(&mut store, (in_ptr, in_len)).map_err(|err| {
+        if err.downcast_ref::<Trap>() == Some(&Trap::Interrupt) {
+            err.context(PluginCallError::Timeout {
+                timeout_ms: limits.invoke_timeout_ms,
+            })
+        } else {
+            err.context(PluginCallError::Trap {
+                function: fn_name.to_string(),
+            })
+        }
+    })?;
     let _ = dealloc.call(&mut store, (in_ptr, in_len));
 
-    let (out_ptr, out_len) = unpack_ptr_len(packed)?;
+    let (out_ptr, out_len) = unpack_ptr_len(packed).with_context(|| {
+        PluginCallError::Serialization(format!("'{fn_name}' returned an invalid output range"))
+    })?;
     if usize::try_from(out_len).unwrap_or(usize::MAX) > MAX_WASM_PAYLOAD_BYTES_FALLBACK {
-        anyhow::bail!("wasm output payload exceeds safety limit");
+        return Err(PluginCallError::Serialization(
+            "wasm output payload exceeds safety limit".to_string(),
+        )
+        .into());
     }
-    let out_bytes = read_guest_bytes(&mut store, &memory, out_ptr, out_len)?;
+    let out_bytes = read_guest_bytes(&mut store, &memory, out_ptr, out_len).with_context(|| {
+        PluginCallError::Serialization(format!("'{fn_name}' returned an invalid output range"))
+    })?;
     let _ = dealloc.call(&mut store, (out_ptr, out_len));
 
-    String::from_utf8(out_bytes).context("wasm function returned non-utf8 output")
+    String::from_utf8(out_bytes).with_context(|| {
+        PluginCallError::Serialization(format!("'{fn_name}' returned non-utf8 output"))
+    })
 }
 
 fn semaphore_cell() -> &'static RwLock<Arc<Semaphore>> {
@@ -211,64 +1137,820 @@ fn current_limits() -> PluginExecutionLimits {
     guard.limits
 }
 
-async fn call_wasm_json_limited(
-    module_path: String,
-    fn_name: &'static str,
-    payload: String,
-) -> Result<String> {
-    let limits = current_limits();
-    let semaphore = semaphore_cell()
-        .read()
-        .unwrap_or_else(std::sync::PoisonError::into_inner)
-        .clone();
-    let max_by_config = usize::try_from(limits.memory_limit_bytes).unwrap_or(usize::MAX);
-    let max_payload = max_by_config.min(MAX_WASM_PAYLOAD_BYTES_FALLBACK);
-    if payload.len() > max_payload {
-        anyhow::bail!("plugin payload exceeds configured memory limit");
-    }
+/// Per-plugin `(limit, limiter)` pairs keyed by plugin id.
+type PluginSemaphores = HashMap<String, (usize, Arc<Semaphore>)>;
 
-    run_blocking_with_timeout(semaphore, limits.invoke_timeout_ms, move || {
-        call_wasm_json(&module_path, fn_name, &payload)
-    })
-    .await
+fn plugin_semaphores() -> &'static Mutex<PluginSemaphores> {
+    static CELL: OnceLock<Mutex<PluginSemaphores>> = OnceLock::new();
+    CELL.get_or_init(|| Mutex::new(HashMap::new()))
 }
 
-async fn run_blocking_with_timeout<T, F>(
-    semaphore: Arc<Semaphore>,
-    timeout_ms: u64,
-    work: F,
-) -> Result<T>
-where
-    T: Send + 'static,
-    F: FnOnce() -> Result<T> + Send + 'static,
-{
-    let _permit = semaphore
-        .acquire_owned()
-        .await
-        .context("plugin concurrency limiter closed")?;
-    let mut handle = tokio::task::spawn_blocking(work);
-    match timeout(Duration::from_millis(timeout_ms), &mut handle).await {
-        Ok(result) => result.context("plugin blocking task join failed")?,
-        Err(_) => {
-            // Best-effort cancellation: spawn_blocking tasks may still run if already executing,
-            // but releasing the permit here prevents permanent limiter starvation.
-            handle.abort();
-            anyhow::bail!("plugin invocation timed out");
+/// Limiter for plugin `plugin_id`, created on first use and replaced when the
+/// configured limit changes. Calls still holding a permit from the replaced
+/// limiter finish under the old limit.
+fn plugin_semaphore(plugin_id: &str, limit: usize) -> Arc<Semaphore> {
+    let mut guard = plugin_semaphores()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    match guard.get(plugin_id) {
+        Some((current, semaphore)) if *current == limit => Arc::clone(semaphore),
+        _ => {
+            let semaphore = Arc::new(Semaphore::new(limit));
+            guard.insert(plugin_id.to_string(), (limit, Arc::clone(&semaphore)));
+            semaphore
         }
     }
 }
 
-pub async fn execute_plugin_tool(tool_name: &str, args: &Value) -> Result<ToolResult> {
-    let registry = current_registry();
+fn in_flight_counters() -> &'static Mutex<HashMap<String, Arc<AtomicUsize>>> {
+    static CELL: OnceLock<Mutex<HashMap<String, Arc<AtomicUsize>>>> = OnceLock::new();
+    CELL.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Running-call counter for plugin `plugin_id`. Kept apart from the limiter
+/// so counts survive a limit change.
+fn in_flight_counter(plugin_id: &str) -> Arc<AtomicUsize> {
+    let mut guard = in_flight_counters()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    Arc::clone(guard.entry(plugin_id.to_string()).or_default())
+}
+
+/// Counts one call as in flight until dropped.
+struct InFlightGuard(Arc<AtomicUsize>);
+
+impl InFlightGuard {
+    fn enter(counter: Arc<AtomicUsize>) -> Self {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Self(counter)
+    }
+}
+
+impl Drop for InFlightGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Calls currently running in each plugin, keyed by plugin id. A call counts
+/// until its guest code returns, including after the caller timed out.
+pub fn plugin_in_flight_calls() -> HashMap<String, usize> {
+    in_flight_counters()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .iter()
+        .map(|(plugin_id, counter)| (plugin_id.clone(), counter.load(Ordering::SeqCst)))
+        .collect()
+}
+
+fn payload_observers() -> &'static Mutex<Vec<Weak<dyn Observer>>> {
+    static CELL: OnceLock<Mutex<Vec<Weak<dyn Observer>>>> = OnceLock::new();
+    CELL.get_or_init(|| Mutex::new(Vec::new()))
+}
+
+/// Send plugin payload sizes to `observer` as
+/// [`ObserverMetric::PluginPayloadBytes`] while it is alive. Hosts that build
+/// an observer (gateway, channels, agent loops) register it here; dropped
+/// observers are forgotten on the next call.
+pub fn register_observer(observer: &Arc<dyn Observer>) {
+    let mut observers = payload_observers()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    observers.retain(|existing| existing.strong_count() > 0);
+    let weak = Arc::downgrade(observer);
+    if !observers.iter().any(|existing| existing.ptr_eq(&weak)) {
+        observers.push(weak);
+    }
+}
+
+fn record_payload_size(
+    plugin_id: &str,
+    operation: &'static str,
+    direction: &'static str,
+    bytes: usize,
+) {
+    let observers: Vec<Arc<dyn Observer>> = payload_observers()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .iter()
+        .filter_map(Weak::upgrade)
+        .collect();
+    if observers.is_empty() {
+        return;
+    }
+    let metric = ObserverMetric::PluginPayloadBytes {
+        plugin: plugin_id.to_string(),
+        operation: operation.to_string(),
+        direction: direction.to_string(),
+        bytes: u64::try_from(bytes).unwrap_or(u64::MAX),
+    };
+    for observer in observers {
+        observer.record_metric(&metric);
+    }
+}
+
+/// Call `fn_name` in plugin `plugin_id`'s module. Host imports act on behalf
+/// of `plugin_id`, so plugins sharing a module still keep separate state.
+async fn call_wasm_json_limited(
+    plugin_id: String,
+    module_path: String,
+    settings: PluginSettings,
+    policy: CallPolicy,
+    fn_name: &'static str,
+    payload: String,
+) -> Result<String> {
+    let mut limits = current_limits();
+    if let Some(timeout_ms) = settings.timeout_ms {
+        limits.invoke_timeout_ms = timeout_ms;
+    }
+    let semaphore = semaphore_cell()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    let max_by_config = usize::try_from(limits.memory_limit_bytes).unwrap_or(usize::MAX);
+    let max_payload = max_by_config.min(MAX_WASM_PAYLOAD_BYTES_FALLBACK);
+    if payload.len() > max_payload {
+        return Err(PluginCallError::Serialization(
+            "plugin payload exceeds configured memory limit".to_string(),
+        )
+        .into());
+    }
+    record_payload_size(&plugin_id, fn_name, "request", payload.len());
+    let plugin_limiter = settings
+        .max_concurrent_calls
+        .map(|limit| plugin_semaphore(&plugin_id, limit));
+    let in_flight = in_flight_counter(&plugin_id);
+    // Signalled when an attempt's guest returns, so a retry after a timeout
+    // starts only once the guest it replaces has stopped running.
+    let mut previous_attempt: Option<tokio::sync::oneshot::Receiver<()>> = None;
+
+    let result = retry_transient(policy.retry.unwrap_or_default(), || {
+        let plugin_id = plugin_id.clone();
+        let module_path = module_path.clone();
+        let payload = payload.clone();
+        let plugin_limiter = plugin_limiter.clone();
+        let in_flight = Arc::clone(&in_flight);
+        let semaphore = Arc::clone(&semaphore);
+        let (finished_tx, finished_rx) = tokio::sync::oneshot::channel();
+        let previous = previous_attempt.replace(finished_rx);
+        async move {
+            if let Some(previous) = previous {
+                // The sender drops when the guest returns, which the epoch
+                // deadline bounds.
+                let _ = previous.await;
+            }
+            run_plugin_call(
+                plugin_limiter,
+                in_flight,
+                semaphore,
+                limits.invoke_timeout_ms,
+                move || {
+                    let _finished = finished_tx;
+                    call_wasm_json(&plugin_id, &module_path, fn_name, &payload, limits)
+                },
+            )
+            .await
+        }
+    })
+    .await;
+    if let Ok(response) = &result {
+        record_payload_size(&plugin_id, fn_name, "response", response.len());
+    }
+    if let (Err(err), Some(policy)) = (&result, policy.quarantine) {
+        if matches!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(PluginCallError::Timeout { .. })
+        ) {
+            record_timeout(&plugin_id, policy);
+        }
+    }
+    result
+}
+
+fn timeout_history() -> &'static Mutex<HashMap<String, VecDeque<Instant>>> {
+    static CELL: OnceLock<Mutex<HashMap<String, VecDeque<Instant>>>> = OnceLock::new();
+    CELL.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Count a timed-out call against plugin `plugin_id` and quarantine it once
+/// `policy.max_timeouts` fall within `policy.window`. Only a call's final
+/// outcome counts: a call whose retries all time out is recorded once, so
+/// retries don't hasten a quarantine.
+fn record_timeout(plugin_id: &str, policy: QuarantinePolicy) {
+    let now = Instant::now();
+    let timeouts = {
+        let mut history = timeout_history()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let recent = history.entry(plugin_id.to_string()).or_default();
+        recent.push_back(now);
+        while recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > policy.window)
+        {
+            recent.pop_front();
+        }
+        if recent.len() < usize::try_from(policy.max_timeouts).unwrap_or(usize::MAX) {
+            return;
+        }
+        let timeouts = recent.len();
+        recent.clear();
+        timeouts
+    };
+    let quarantined = registry_cell()
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .registry
+        .quarantine(plugin_id, policy.cooldown.map(|cooldown| now + cooldown));
+    if quarantined {
+        tracing::error!(
+            plugin = %plugin_id,
+            timeouts,
+            window_secs = policy.window.as_secs(),
+            cooldown_secs = policy.cooldown.map(|cooldown| cooldown.as_secs()),
+            "CRITICAL: plugin quarantined after repeated timeouts; its tools and providers are unrouted"
+        );
+    }
+}
+
+/// Put a quarantined plugin back into routing and forget its recent timeouts.
+/// Returns `false` if the plugin was not quarantined.
+pub fn reenable_plugin(id: &str) -> Result<bool> {
+    let released = {
+        let mut guard = registry_cell()
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if guard.registry.manifest(id).is_none() {
+            anyhow::bail!("plugin '{id}' is not registered");
+        }
+        guard.registry.release(id)
+    };
+    timeout_history()
+        .lock()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .remove(id);
+    if released {
+        tracing::info!(plugin = %id, "plugin re-enabled after quarantine");
+    }
+    Ok(released)
+}
+
+/// Release quarantines whose cooldown has ended.
+fn release_expired_quarantines() {
+    let now = Instant::now();
+    let expired = registry_cell()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .registry
+        .has_expired_quarantine(now);
+    if !expired {
+        return;
+    }
+    let released = registry_cell()
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .registry
+        .release_expired(now);
+    for id in released {
+        tracing::info!(plugin = %id, "plugin quarantine cooldown ended; re-enabled");
+    }
+}
+
+/// Run `attempt` until it succeeds, fails permanently, or `policy` runs out of
+/// retries. Only transient [`PluginCallError`]s are retried, timeouts only
+/// when `policy.retry_timeouts` allows it, with exponential backoff between
+/// attempts.
+async fn retry_transient<T, F, Fut>(policy: RetryPolicy, mut attempt: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Result<T>>,
+{
+    let mut retries = 0;
+    loop {
+        let err = match attempt().await {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        let retryable = match err.downcast_ref::<PluginCallError>() {
+            Some(PluginCallError::Timeout { .. }) => policy.retry_timeouts,
+            Some(other) => other.is_transient(),
+            None => false,
+        };
+        if !retryable || retries >= policy.attempts {
+            return Err(err);
+        }
+        let delay_ms = policy.backoff_ms.saturating_mul(1 << retries.min(16));
+        retries += 1;
+        tracing::debug!(
+            retry = retries,
+            delay_ms,
+            error = %err,
+            "retrying transient plugin failure"
+        );
+        tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+    }
+}
+
+/// Acquire the per-plugin permit before the global one, so a plugin
+/// serialized behind its own limit waits without holding a global slot.
+/// Both waits share one deadline, `timeout_ms` from now: a call stuck behind
+/// a saturated limiter fails with [`PluginCallError::Timeout`] instead of
+/// queueing indefinitely.
+async fn run_plugin_call<T, F>(
+    plugin_limiter: Option<Arc<Semaphore>>,
+    in_flight: Arc<AtomicUsize>,
+    semaphore: Arc<Semaphore>,
+    timeout_ms: u64,
+    work: F,
+) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let deadline = tokio::time::Instant::now() + Duration::from_millis(timeout_ms);
+    let plugin_permit = match plugin_limiter {
+        Some(limiter) => Some(acquire_permit(limiter, deadline, timeout_ms).await?),
+        None => None,
+    };
+    let permit = acquire_permit(semaphore, deadline, timeout_ms).await?;
+    // The permits move into the blocking task: a guest that outlives the
+    // caller's timeout keeps its slots until it actually returns, which the
+    // epoch deadline bounds.
+    run_blocking_with_timeout(timeout_ms, move || {
+        let _permits = (plugin_permit, permit);
+        let _in_flight = InFlightGuard::enter(in_flight);
+        work()
+    })
+    .await
+}
+
+async fn acquire_permit(
+    semaphore: Arc<Semaphore>,
+    deadline: tokio::time::Instant,
+    timeout_ms: u64,
+) -> Result<OwnedSemaphorePermit> {
+    match tokio::time::timeout_at(deadline, semaphore.acquire_owned()).await {
+        Ok(permit) => permit.context("plugin concurrency limiter closed"),
+        Err(_) => Err(PluginCallError::Timeout { timeout_ms }.into()),
+    }
+}
+
+async fn run_blocking_with_timeout<T, F>(timeout_ms: u64, work: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T> + Send + 'static,
+{
+    let mut handle = tokio::task::spawn_blocking(work);
+    match timeout(Duration::from_millis(timeout_ms), &mut handle).await {
+        Ok(result) => result.context("plugin blocking task join failed")?,
+        Err(_) => {
+            handle.abort();
+            Err(PluginCallError::Timeout { timeout_ms }.into())
+        }
+    }
+}
+
+/// Health of one plugin, as reported by [`PluginRuntime::health_report`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginHealth {
+    pub id: String,
+    /// `false` when config disables the plugin or it is quarantined.
+    pub enabled: bool,
+    pub module_path: String,
+    pub module_exists: bool,
+    /// `None` when the check did not run (plugin disabled or module missing).
+    pub healthy: Option<bool>,
+    pub last_error: Option<String>,
+}
+
+async fn check_registry_health(
+    registry: &PluginRegistry,
+    limits: PluginExecutionLimits,
+) -> Vec<PluginHealth> {
+    let mut manifests: Vec<(&PluginManifest, bool)> = registry
+        .all_manifests()
+        .into_iter()
+        .map(|manifest| (manifest, !registry.is_quarantined(&manifest.id)))
+        .chain(
+            registry
+                .disabled_manifests()
+                .into_iter()
+                .map(|manifest| (manifest, false)),
+        )
+        .collect();
+    manifests.sort_by(|a, b| a.0.id.cmp(&b.0.id));
+
+    let checks = manifests.into_iter().map(|(manifest, enabled)| {
+        let id = manifest.id.clone();
+        let module_path = manifest.module_path.clone();
+        let manifest = manifest.clone();
+        let timeout_ms = registry
+            .settings(&id)
+            .and_then(|settings| settings.timeout_ms)
+            .unwrap_or(limits.invoke_timeout_ms);
+        let quarantined = registry.is_quarantined(&id);
+        async move {
+            let module_exists = Path::new(&module_path).is_file();
+            if !enabled || !module_exists {
+                let last_error = if quarantined {
+                    Some("plugin quarantined".to_string())
+                } else if enabled {
+                    Some("module file not found".to_string())
+                } else {
+                    None
+                };
+                return PluginHealth {
+                    id,
+                    enabled,
+                    module_path,
+                    module_exists,
+                    healthy: None,
+                    last_error,
+                };
+            }
+            let check = tokio::task::spawn_blocking(move || probe_module(&manifest, limits));
+            let result = match timeout(Duration::from_millis(timeout_ms), check).await {
+                Ok(Ok(result)) => result,
+                Ok(Err(join_err)) => Err(anyhow::anyhow!("plugin health check failed: {join_err}")),
+                Err(_) => Err(anyhow::anyhow!("plugin health check timed out")),
+            };
+            PluginHealth {
+                id,
+                enabled,
+                module_path,
+                module_exists: true,
+                healthy: Some(result.is_ok()),
+                last_error: result.err().map(|err| format!("{err:#}")),
+            }
+        }
+    });
+    futures_util::future::join_all(checks).await
+}
+
+/// Health probe for `manifest`'s module that runs no guest code: it must
+/// compile (from the module cache when unchanged), link against the host
+/// imports, export the ABI its manifest needs, and declare no more initial
+/// memory than `limits` allow.
+fn probe_module(manifest: &PluginManifest, limits: PluginExecutionLimits) -> Result<()> {
+    let module_path = manifest.module_path.as_str();
+    let module = cached_module(shared_engine()?, module_path)?;
+    instance_pre(module_path, &module)?;
+    let missing = missing_exports(manifest, &module);
+    if !missing.is_empty() {
+        anyhow::bail!("{module_path} is missing exports: {}", missing.join(", "));
+    }
+    for export in module.exports() {
+        if let ExternType::Memory(memory) = export.ty() {
+            let initial = memory.minimum().saturating_mul(memory.page_size());
+            if initial > limits.memory_limit_bytes {
+                anyhow::bail!(
+                    "{module_path} needs {initial} bytes of initial memory, over the {} byte limit",
+                    limits.memory_limit_bytes
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Outcome of validating one registered plugin, as reported by
+/// [`PluginRuntime::validate_all`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PluginValidation {
+    pub id: String,
+    pub module_path: String,
+    /// ABI exports the manifest requires but the module lacks.
+    pub missing_exports: Vec<String>,
+    /// Problems that stopped validation early (missing file, bad magic,
+    /// compile failure).
+    pub errors: Vec<String>,
+}
+
+impl PluginValidation {
+    pub fn passed(&self) -> bool {
+        self.missing_exports.is_empty() && self.errors.is_empty()
+    }
+}
+
+fn validate_registry(registry: &PluginRegistry) -> Vec<PluginValidation> {
+    let mut manifests = registry.all_manifests();
+    manifests.sort_by(|a, b| a.id.cmp(&b.id));
+    manifests
+        .into_iter()
+        .map(|manifest| {
+            let mut validation = PluginValidation {
+                id: manifest.id.clone(),
+                module_path: manifest.module_path.clone(),
+                missing_exports: Vec::new(),
+                errors: Vec::new(),
+            };
+            match validate_module(manifest) {
+                Ok(missing) => validation.missing_exports = missing,
+                Err(err) => validation.errors.push(format!("{err:#}")),
+            }
+            validation
+        })
+        .collect()
+}
+
+/// Required exports `manifest`'s module lacks, or an error if it can't be
+/// read or compiled.
+fn validate_module(manifest: &PluginManifest) -> Result<Vec<String>> {
+    let module_path = manifest.module_path.as_str();
+    let bytes = read_module_bytes(module_path)?;
+    if !bytes.starts_with(WASM_MAGIC) {
+        anyhow::bail!("{module_path} is not a wasm module (bad magic bytes)");
+    }
+    let module =
+        Module::new(shared_engine()?, &bytes).with_context(|| PluginCallError::InvalidModule {
+            module: module_path.to_string(),
+        })?;
+    Ok(missing_exports(manifest, &module))
+}
+
+/// ABI exports `manifest` needs that `module` lacks, `memory` first.
+fn missing_exports(manifest: &PluginManifest, module: &Module) -> Vec<String> {
+    let mut required = vec![ABI_ALLOC_FN, ABI_DEALLOC_FN];
+    if !manifest.tools.is_empty() {
+        required.push(ABI_TOOL_EXEC_FN);
+    }
+    if !manifest.providers.is_empty() {
+        required.push(ABI_PROVIDER_CHAT_FN);
+    }
+    if manifest.capabilities.contains(&PluginCapability::Memory) {
+        required.push(ABI_MEMORY_CALL_FN);
+    }
+    let mut missing: Vec<String> = required
+        .into_iter()
+        .filter(|name| !matches!(module.get_export(name), Some(ExternType::Func(_))))
+        .map(str::to_string)
+        .collect();
+    if !matches!(module.get_export("memory"), Some(ExternType::Memory(_))) {
+        missing.insert(0, "memory".to_string());
+    }
+    missing
+}
+
+/// Switch plugin `id` to the module at `module_path` without dropping calls.
+///
+/// The new module is checked against `sha256` (required when the plugin is
+/// pinned), compiled, and checked for the exports its manifest needs before
+/// any call is routed to it. If it exports `zeroclaw_plugin_migrate`, that
+/// runs once against the plugin's state with `{"plugin", "from_module",
+/// "to_module"}`, under the plugin's call permits and timeout like any other
+/// call; a trap, a timeout or an `{"error": ...}` reply aborts the swap. The
+/// plugin's state lock is held from the migrate call until calls are routed
+/// to the new module, so no other call writes state in between. Calls
+/// already running finish on the old module; calls starting after the swap
+/// use the new one. The old module leaves the cache once no plugin points at
+/// it.
+///
+/// The swap survives hot reloads and [`reload_plugin`] until the plugin's
+/// manifest itself points at another module. Each attempt is written to
+/// `audit` as a config-change event.
+pub async fn swap_module(
+    id: &str,
+    module_path: &str,
+    sha256: Option<&str>,
+    audit: Option<&AuditLogger>,
+) -> Result<()> {
+    let started = Instant::now();
+    let result = swap_module_inner(id, module_path, sha256).await;
+    if let Some(audit) = audit {
+        let event = AuditEvent::new(AuditEventType::ConfigChange)
+            .with_actor("plugins".to_string(), None, None)
+            .with_action(
+                format!("swap plugin '{id}' module to {module_path}"),
+                "medium".to_string(),
+                true,
+                result.is_ok(),
+            )
+            .with_result(
+                result.is_ok(),
+                None,
+                u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
+                result.as_ref().err().map(|error| format!("{error:#}")),
+            );
+        if let Err(error) = audit.log(&event) {
+            tracing::warn!(plugin = %id, "failed to audit plugin module swap: {error:#}");
+        }
+    }
+    result
+}
+
+/// How a swap's blocking half ended, settled once so a swap the caller gave
+/// up on after a timeout can't still switch modules later.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SwapOutcome {
+    Pending,
+    Committed,
+    Abandoned,
+}
+
+async fn swap_module_inner(id: &str, module_path: &str, sha256: Option<&str>) -> Result<()> {
+    let registry = current_registry();
+    let manifest = registry
+        .manifest(id)
+        .with_context(|| format!("plugin '{id}' is not registered"))?;
+    let settings = registry.settings(id).cloned().unwrap_or_default();
+    if settings.sha256.is_some() && sha256.is_none() {
+        anyhow::bail!("plugin '{id}' is pinned; pass the new module's sha256 to swap it");
+    }
+    let sha256 = sha256.map(|digest| digest.trim().to_ascii_lowercase());
+    let modified = std::fs::metadata(module_path)
+        .ok()
+        .and_then(|metadata| metadata.modified().ok());
+    let module = compile_module(shared_engine()?, module_path, sha256.as_deref(), modified)?;
+    let missing = missing_exports(manifest, &module);
+    if !missing.is_empty() {
+        anyhow::bail!(
+            "module {module_path} for plugin '{id}' is missing exports: {}",
+            missing.join(", ")
+        );
+    }
+    let migrates = matches!(module.get_export(ABI_MIGRATE_FN), Some(ExternType::Func(_)));
+
+    let mut limits = current_limits();
+    if let Some(timeout_ms) = settings.timeout_ms {
+        limits.invoke_timeout_ms = timeout_ms;
+    }
+    let plugin_limiter = settings
+        .max_concurrent_calls
+        .map(|limit| plugin_semaphore(id, limit));
+    let semaphore = semaphore_cell()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .clone();
+    let outcome = Arc::new(Mutex::new(SwapOutcome::Pending));
+    let work = {
+        let id = id.to_string();
+        let from_module = manifest.module_path.clone();
+        let module_path = module_path.to_string();
+        let outcome = Arc::clone(&outcome);
+        move || {
+            with_plugin_state_lock(&id, || {
+                if migrates {
+                    migrate_module(&id, &from_module, &module_path, limits)?;
+                }
+                let mut outcome = outcome
+                    .lock()
+                    .unwrap_or_else(std::sync::PoisonError::into_inner);
+                if *outcome == SwapOutcome::Abandoned {
+                    anyhow::bail!("swap of plugin '{id}' abandoned after timing out");
+                }
+                switch_module(&id, &module_path, sha256)?;
+                *outcome = SwapOutcome::Committed;
+                Ok(())
+            })
+        }
+    };
+    let result = run_plugin_call(
+        plugin_limiter,
+        in_flight_counter(id),
+        semaphore,
+        limits.invoke_timeout_ms,
+        work,
+    )
+    .await;
+    if result.is_err() {
+        let mut outcome = outcome
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if *outcome == SwapOutcome::Committed {
+            return Ok(());
+        }
+        *outcome = SwapOutcome::Abandoned;
+    }
+    result
+}
+
+/// Route plugin `id` to `module_path` and record the override so it
+/// survives reloads.
+fn switch_module(id: &str, module_path: &str, sha256: Option<String>) -> Result<()> {
+    let (previous, still_used) = {
+        let mut guard = registry_cell()
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        let previous = guard
+            .registry
+            .swap_module(id, module_path, sha256.clone())?;
+        let replaced = guard
+            .module_overrides
+            .remove(id)
+            .map_or_else(|| previous.clone(), |existing| existing.replaced);
+        guard.module_overrides.insert(
+            id.to_string(),
+            ModuleOverride {
+                replaced,
+                module_path: module_path.to_string(),
+                sha256,
+            },
+        );
+        let still_used = guard
+            .registry
+            .all_manifests()
+            .iter()
+            .any(|manifest| manifest.module_path == previous);
+        (previous, still_used)
+    };
+    if !still_used && previous != module_path {
+        module_cache()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(&previous);
+    }
+    tracing::info!(plugin = %id, from = %previous, to = %module_path, "plugin module swapped");
+    Ok(())
+}
+
+/// Run the new module's migrate export before plugin `id` is switched to it.
+fn migrate_module(
+    id: &str,
+    from_module: &str,
+    to_module: &str,
+    limits: PluginExecutionLimits,
+) -> Result<()> {
+    let input = serde_json::json!({
+        "plugin": id,
+        "from_module": from_module,
+        "to_module": to_module,
+    });
+    let output = call_wasm_json(id, to_module, ABI_MIGRATE_FN, &input.to_string(), limits)
+        .with_context(|| format!("migrating plugin '{id}' to {to_module} failed"))?;
+    if let Some(error) = serde_json::from_str::<Value>(&output)
+        .ok()
+        .and_then(|reply| {
+            reply
+                .get("error")
+                .and_then(Value::as_str)
+                .map(str::to_string)
+        })
+    {
+        anyhow::bail!("migrating plugin '{id}' to {to_module} failed: {error}");
+    }
+    Ok(())
+}
+
+/// Re-apply recorded swaps to a registry freshly loaded from manifests.
+///
+/// An override is dropped, and the manifest wins, once the plugin is gone or
+/// its manifest points at a module other than the one the swap replaced.
+fn apply_module_overrides(
+    registry: &mut PluginRegistry,
+    overrides: &HashMap<String, ModuleOverride>,
+) -> HashMap<String, ModuleOverride> {
+    let mut kept = HashMap::new();
+    for (id, module_override) in overrides {
+        let Some(manifest) = registry.manifest(id) else {
+            continue;
+        };
+        if manifest.module_path != module_override.replaced {
+            tracing::info!(
+                plugin = %id,
+                module = %manifest.module_path,
+                "plugin manifest changed module; dropping swapped module"
+            );
+            continue;
+        }
+        if registry
+            .swap_module(
+                id,
+                &module_override.module_path,
+                module_override.sha256.clone(),
+            )
+            .is_ok()
+        {
+            kept.insert(id.clone(), module_override.clone());
+        }
+    }
+    kept
+}
+
+pub async fn execute_plugin_tool(tool_name: &str, args: &Value) -> Result<ToolResult> {
+    let registry = current_registry();
+    let plugin_id = registry
+        .tool_plugin_id(tool_name)
+        .ok_or_else(|| anyhow::anyhow!("plugin tool '{tool_name}' not found in registry"))?
+        .to_string();
     let module_path = registry
         .tool_module_path(tool_name)
-        .ok_or_else(|| anyhow::anyhow!("plugin tool '{tool_name}' not found in registry"))?
+        .unwrap_or_default()
         .to_string();
     let payload = serde_json::json!({
         "tool": tool_name,
         "args": args,
     });
-    let output = call_wasm_json_limited(module_path, ABI_TOOL_EXEC_FN, payload.to_string()).await?;
+    let settings = registry.settings(&plugin_id).cloned().unwrap_or_default();
+    let policy = call_policy(&registry, &plugin_id);
+    let output = call_wasm_json_limited(
+        plugin_id,
+        module_path,
+        settings,
+        policy,
+        ABI_TOOL_EXEC_FN,
+        payload.to_string(),
+    )
+    .await?;
     if let Ok(parsed) = serde_json::from_str::<ToolResult>(&output) {
         return Ok(parsed);
     }
@@ -287,9 +1969,13 @@ pub async fn execute_plugin_provider_chat(
     temperature: f64,
 ) -> Result<String> {
     let registry = current_registry();
+    let plugin_id = registry
+        .provider_plugin_id(provider_name)
+        .ok_or_else(|| anyhow::anyhow!("plugin provider '{provider_name}' not found in registry"))?
+        .to_string();
     let module_path = registry
         .provider_module_path(provider_name)
-        .ok_or_else(|| anyhow::anyhow!("plugin provider '{provider_name}' not found in registry"))?
+        .unwrap_or_default()
         .to_string();
     let request = ProviderPluginRequest {
         provider: provider_name,
@@ -298,8 +1984,13 @@ pub async fn execute_plugin_provider_chat(
         model,
         temperature,
     };
+    let settings = registry.settings(&plugin_id).cloned().unwrap_or_default();
+    let policy = call_policy(&registry, &plugin_id);
     let output = call_wasm_json_limited(
+        plugin_id,
         module_path,
+        settings,
+        policy,
         ABI_PROVIDER_CHAT_FN,
         serde_json::to_string(&request)?,
     )
@@ -313,6 +2004,41 @@ pub async fn execute_plugin_provider_chat(
     Ok(output)
 }
 
+/// Send one memory operation to plugin `plugin_id` and return its JSON
+/// reply. A reply carrying a string `error` field fails the call.
+pub async fn execute_plugin_memory(plugin_id: &str, request: &Value) -> Result<Value> {
+    let registry = current_registry();
+    let manifest = registry
+        .manifest(plugin_id)
+        .with_context(|| format!("memory plugin '{plugin_id}' is not registered"))?;
+    if registry.is_quarantined(plugin_id) {
+        anyhow::bail!("memory plugin '{plugin_id}' is quarantined");
+    }
+    let module_path = manifest.module_path.clone();
+    let settings = registry.settings(plugin_id).cloned().unwrap_or_default();
+    let policy = call_policy(&registry, plugin_id);
+    let output = call_wasm_json_limited(
+        plugin_id.to_string(),
+        module_path,
+        settings,
+        policy,
+        ABI_MEMORY_CALL_FN,
+        request.to_string(),
+    )
+    .await?;
+    let reply: Value = serde_json::from_str(&output).with_context(|| {
+        PluginCallError::Serialization(format!("'{ABI_MEMORY_CALL_FN}' returned invalid JSON"))
+    })?;
+    if let Some(error) = reply.get("error").and_then(Value::as_str) {
+        anyhow::bail!("plugin memory error: {error}");
+    }
+    // `{"success": false}` is a failure even when the plugin gives no reason.
+    if reply.get("success").and_then(Value::as_bool) == Some(false) {
+        anyhow::bail!("plugin memory error: plugin reported failure without detail");
+    }
+    Ok(reply)
+}
+
 fn registry_cell() -> &'static RwLock<RuntimeState> {
     static CELL: OnceLock<RwLock<RuntimeState>> = OnceLock::new();
     CELL.get_or_init(|| RwLock::new(RuntimeState::default()))
@@ -325,6 +2051,16 @@ struct RuntimeState {
     config: Option<PluginsConfig>,
     fingerprints: HashMap<String, SystemTime>,
     limits: PluginExecutionLimits,
+    module_overrides: HashMap<String, ModuleOverride>,
+}
+
+/// A [`swap_module`] to re-apply whenever the registry is reloaded.
+#[derive(Debug, Clone)]
+struct ModuleOverride {
+    /// Manifest module path the swap replaced.
+    replaced: String,
+    module_path: String,
+    sha256: Option<String>,
 }
 
 impl Default for RuntimeState {
@@ -338,6 +2074,7 @@ impl Default for RuntimeState {
                 invoke_timeout_ms: 2_000,
                 memory_limit_bytes: 64 * 1024 * 1024,
             },
+            module_overrides: HashMap::new(),
         }
     }
 }
@@ -393,17 +2130,148 @@ fn maybe_hot_reload() {
         return;
     }
 
-    let runtime = PluginRuntime::new();
-    let load_result = runtime.load_registry_from_config(&config);
-    if let Ok(new_registry) = load_result {
-        let mut guard = registry_cell()
-            .write()
-            .unwrap_or_else(std::sync::PoisonError::into_inner);
-        guard.registry = new_registry;
-        guard.fingerprints = current_fingerprints;
+    let rebuilt = RebuiltRegistry::build(&config);
+    let mut guard = registry_cell()
+        .write()
+        .unwrap_or_else(std::sync::PoisonError::into_inner);
+    if guard
+        .config
+        .as_ref()
+        .map(config_fingerprint)
+        .is_none_or(|live| live != config_fingerprint(&config))
+    {
+        // Re-initialized with another config while we were loading.
+        return;
+    }
+    // Record the scanned state even when loading failed, so an unchanged
+    // broken manifest isn't re-parsed and re-reported on every lookup.
+    guard.fingerprints = current_fingerprints;
+    match rebuilt {
+        Ok(rebuilt) => {
+            let summary = rebuilt.summary.clone();
+            if rebuilt.install(&mut guard) && !summary.is_empty() {
+                tracing::info!(
+                    added = ?summary.added,
+                    removed = ?summary.removed,
+                    updated = ?summary.updated,
+                    generation = guard.registry.generation(),
+                    "plugin registry hot-reloaded"
+                );
+            }
+        }
+        Err(error) => {
+            tracing::warn!("plugin hot reload skipped until manifests change: {error:#}");
+        }
     }
 }
 
+/// A copy of the live registry reconfigured against a config without
+/// holding the registry lock, so manifest and module IO doesn't block
+/// plugin lookups.
+struct RebuiltRegistry {
+    base_generation: u64,
+    registry: PluginRegistry,
+    summary: ReconfigureSummary,
+    module_overrides: HashMap<String, ModuleOverride>,
+}
+
+impl RebuiltRegistry {
+    fn build(config: &PluginsConfig) -> Result<Self> {
+        let (mut registry, overrides) = {
+            let guard = registry_cell()
+                .read()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            (guard.registry.clone(), guard.module_overrides.clone())
+        };
+        let base_generation = registry.generation();
+        let mut next = PluginRuntime::new().load_registry_from_config(config)?;
+        let module_overrides = apply_module_overrides(&mut next, &overrides);
+        let summary = registry.reconfigure(next);
+        Ok(Self {
+            base_generation,
+            registry,
+            summary,
+            module_overrides,
+        })
+    }
+
+    /// Swap the rebuilt registry in, unless another reload replaced the
+    /// registry it was built from in the meantime.
+    fn install(self, state: &mut RuntimeState) -> bool {
+        if state.registry.generation() != self.base_generation {
+            return false;
+        }
+        state.registry = self.registry;
+        state.module_overrides = self.module_overrides;
+        true
+    }
+}
+
+/// Re-read plugin `id`'s manifest and recompile its module now, instead of
+/// waiting for mtime-based detection.
+///
+/// The registry is re-synced against the current config, so other changed
+/// plugins are picked up too. Calls already running keep the module they
+/// started with; later calls use the recompiled one. A module that fails its
+/// pinned sha256 is not swapped in: the reload errors and the previously
+/// verified module keeps serving calls.
+pub fn reload_plugin(id: &str) -> Result<ReconfigureSummary> {
+    const MAX_ATTEMPTS: usize = 3;
+    let config = registry_cell()
+        .read()
+        .unwrap_or_else(std::sync::PoisonError::into_inner)
+        .config
+        .clone()
+        .context("plugin runtime is not initialized")?;
+
+    let mut applied = None;
+    for _ in 0..MAX_ATTEMPTS {
+        let fingerprints = collect_manifest_fingerprints(&config.load_paths);
+        let rebuilt = RebuiltRegistry::build(&config)?;
+        // Check the id before anything is swapped in or recompiled.
+        let Some(module_path) = rebuilt
+            .registry
+            .manifest(id)
+            .map(|manifest| manifest.module_path.clone())
+        else {
+            let refusal = rebuilt
+                .registry
+                .diagnostics
+                .iter()
+                .find(|diagnostic| diagnostic.plugin_id.as_deref() == Some(id));
+            return Err(match refusal {
+                Some(diagnostic) => anyhow::anyhow!("{}", diagnostic.message),
+                None => anyhow::anyhow!("plugin '{id}' is not registered"),
+            });
+        };
+        let summary = rebuilt.summary.clone();
+
+        let mut guard = registry_cell()
+            .write()
+            .unwrap_or_else(std::sync::PoisonError::into_inner);
+        if rebuilt.install(&mut guard) {
+            guard.fingerprints = fingerprints;
+            applied = Some((module_path, summary));
+            break;
+        }
+    }
+    let (module_path, summary) = applied.with_context(|| {
+        format!("plugin registry kept changing while reloading '{id}'; try again")
+    })?;
+
+    let modified = std::fs::metadata(&module_path)
+        .ok()
+        .and_then(|metadata| metadata.modified().ok());
+    compile_module(
+        shared_engine()?,
+        &module_path,
+        pinned_module_sha256(&module_path).as_deref(),
+        modified,
+    )?;
+    tracing::info!(plugin = %id, module = %module_path, "plugin reloaded");
+    Ok(summary)
+}
+
 fn init_fingerprint_cell() -> &'static RwLock<Option<String>> {
     static CELL: OnceLock<RwLock<Option<String>>> = OnceLock::new();
     CELL.get_or_init(|| RwLock::new(None))
@@ -427,15 +2295,23 @@ pub fn initialize_from_config(config: &PluginsConfig) -> Result<()> {
         }
     }
 
-    let runtime = PluginRuntime::new();
-    let registry = runtime.load_registry_from_config(config)?;
     let fingerprints = collect_manifest_fingerprints(&config.load_paths);
+    let mut next = PluginRuntime::new().load_registry_from_config(config)?;
     let mut guard = registry_cell()
         .write()
         .unwrap_or_else(std::sync::PoisonError::into_inner);
-    guard.registry = registry;
-    // Keep hot-reload disabled by default until schema-level controls are added.
-    guard.hot_reload = false;
+    guard.module_overrides = apply_module_overrides(&mut next, &guard.module_overrides);
+    let summary = guard.registry.reconfigure(next);
+    if !summary.is_empty() {
+        tracing::info!(
+            added = ?summary.added,
+            removed = ?summary.removed,
+            updated = ?summary.updated,
+            generation = guard.registry.generation(),
+            "plugin registry reconfigured"
+        );
+    }
+    guard.hot_reload = config.hot_reload;
     guard.config = Some(config.clone());
     guard.fingerprints = fingerprints;
     {
@@ -458,6 +2334,7 @@ pub fn initialize_from_config(config: &PluginsConfig) -> Result<()> {
 
 pub fn current_registry() -> PluginRegistry {
     maybe_hot_reload();
+    release_expired_quarantines();
     registry_cell()
         .read()
         .unwrap_or_else(std::sync::PoisonError::into_inner)
@@ -491,99 +2368,2209 @@ description = "{tool} description"
         .expect("write manifest");
     }
 
+    /// Bump-allocator module whose tool export returns its input unchanged.
+    const ECHO_WAT: &str = r#"
+(module
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 1024))
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    global.get $heap
+    local.set $ptr
+    global.get $heap
+    local.get $len
+    i32.add
+    global.set $heap
+    local.get $ptr)
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param $ptr i32) (param $len i32) (result i64)
+    local.get $ptr
+    i64.extend_i32_u
+    i64.const 32
+    i64.shl
+    local.get $len
+    i64.extend_i32_u
+    i64.or))
+"#;
+
+    const TEST_LIMITS: PluginExecutionLimits = PluginExecutionLimits {
+        invoke_timeout_ms: 2_000,
+        memory_limit_bytes: 64 * 1024 * 1024,
+    };
+
+    /// Echo module that logs a fixed message through `env.host_log` first.
+    const LOG_WAT: &str = r#"
+(module
+  (import "env" "host_log" (func $host_log (param i32 i32 i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 16) "hello from guest")
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param $ptr i32) (param $len i32) (result i64)
+    (call $host_log (i32.const 3) (i32.const 16) (i32.const 16))
+    local.get $ptr
+    i64.extend_i32_u
+    i64.const 32
+    i64.shl
+    local.get $len
+    i64.extend_i32_u
+    i64.or))
+"#;
+
+    /// Exports the ABI but never returns from `zeroclaw_tool_execute`.
+    const SPIN_WAT: &str = r#"
+(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param i32 i32) (result i64)
+    (loop $spin (br $spin))
+    i64.const 0))
+"#;
+
+    /// Like `ECHO_WAT`, but grows linear memory by 4 pages (256 KiB) first.
+    const GROW_WAT: &str = r#"
+(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param $ptr i32) (param $len i32) (result i64)
+    i32.const 4
+    memory.grow
+    drop
+    local.get $ptr
+    i64.extend_i32_u
+    i64.const 32
+    i64.shl
+    local.get $len
+    i64.extend_i32_u
+    i64.or))
+"#;
+
+    /// Like `ECHO_WAT`, but counts down from `iterations` before returning.
+    fn busy_wat(iterations: u32) -> String {
+        format!(
+            r#"
+(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param $ptr i32) (param $len i32) (result i64)
+    (local $i i32)
+    (local.set $i (i32.const {iterations}))
+    (loop $busy
+      (local.set $i (i32.sub (local.get $i) (i32.const 1)))
+      (br_if $busy (i32.ne (local.get $i) (i32.const 0))))
+    local.get $ptr
+    i64.extend_i32_u
+    i64.const 32
+    i64.shl
+    local.get $len
+    i64.extend_i32_u
+    i64.or))
+"#
+        )
+    }
+
+    /// Like `ECHO_WAT`, plus a `zeroclaw_plugin_migrate` export that stores
+    /// `schema = "2"` in plugin state and replies with `reply`.
+    fn migrate_wat(reply: &str) -> String {
+        let escaped = reply.replace('"', "\\\"");
+        let reply_len = reply.len();
+        format!(
+            r#"
+(module
+  (import "env" "zeroclaw_state_set" (func $state_set (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "schema")
+  (data (i32.const 8) "2")
+  (data (i32.const 64) "{escaped}")
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param $ptr i32) (param $len i32) (result i64)
+    local.get $ptr
+    i64.extend_i32_u
+    i64.const 32
+    i64.shl
+    local.get $len
+    i64.extend_i32_u
+    i64.or)
+  (func (export "zeroclaw_plugin_migrate") (param i32 i32) (result i64)
+    (if (call $state_set (i32.const 0) (i32.const 6) (i32.const 8) (i32.const 1))
+      (then unreachable))
+    (i64.or (i64.shl (i64.const 64) (i64.const 32)) (i64.const {reply_len}))))
+"#
+        )
+    }
+
+    /// Counts its calls in plugin state under `count`, as a single ASCII
+    /// digit, and returns the new count.
+    const COUNTER_WAT: &str = r#"
+(module
+  (import "env" "zeroclaw_state_get" (func $state_get (param i32 i32) (result i64)))
+  (import "env" "zeroclaw_state_set" (func $state_set (param i32 i32 i32 i32) (result i32)))
+  (memory (export "memory") 1)
+  (data (i32.const 0) "count")
+  (global $heap (mut i32) (i32.const 1024))
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+    (local.get $ptr))
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param i32 i32) (result i64)
+    (local $stored i64)
+    (local.set $stored (call $state_get (i32.const 0) (i32.const 5)))
+    (if (i64.eq (local.get $stored) (i64.const -1))
+      (then (i32.store8 (i32.const 16) (i32.const 49)))
+      (else
+        (i32.store8
+          (i32.const 16)
+          (i32.add
+            (i32.load8_u (i32.wrap_i64 (i64.shr_u (local.get $stored) (i64.const 32))))
+            (i32.const 1)))))
+    (if (call $state_set (i32.const 0) (i32.const 5) (i32.const 16) (i32.const 1))
+      (then unreachable))
+    (i64.or (i64.shl (i64.const 16) (i64.const 32)) (i64.const 1))))
+"#;
+
+    /// Returns `"<uuid> <millis>"` built from `zeroclaw_new_uuid` and
+    /// `zeroclaw_now_millis`, with the millis written out in decimal.
+    const ID_CLOCK_WAT: &str = r#"
+(module
+  (import "env" "zeroclaw_new_uuid" (func $new_uuid (result i64)))
+  (import "env" "zeroclaw_now_millis" (func $now_millis (result i64)))
+  (memory (export "memory") 1)
+  (global $heap (mut i32) (i32.const 1024))
+  (func (export "alloc") (param $len i32) (result i32)
+    (local $ptr i32)
+    (local.set $ptr (global.get $heap))
+    (global.set $heap (i32.add (global.get $heap) (local.get $len)))
+    (local.get $ptr))
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param i32 i32) (result i64)
+    (local $uuid i64)
+    (local $uuid_len i32)
+    (local $now i64)
+    (local $p i32)
+    (local.set $uuid (call $new_uuid))
+    (local.set $uuid_len (i32.wrap_i64 (i64.and (local.get $uuid) (i64.const 0xffffffff))))
+    (local.set $now (call $now_millis))
+    ;; Digits are written backwards so the output ends at offset 200.
+    (local.set $p (i32.const 200))
+    (loop $digits
+      (local.set $p (i32.sub (local.get $p) (i32.const 1)))
+      (i64.store8
+        (local.get $p)
+        (i64.add (i64.const 48) (i64.rem_u (local.get $now) (i64.const 10))))
+      (local.set $now (i64.div_u (local.get $now) (i64.const 10)))
+      (br_if $digits (i64.ne (local.get $now) (i64.const 0))))
+    (local.set $p (i32.sub (local.get $p) (i32.const 1)))
+    (i32.store8 (local.get $p) (i32.const 32))
+    (local.set $p (i32.sub (local.get $p) (local.get $uuid_len)))
+    (memory.copy
+      (local.get $p)
+      (i32.wrap_i64 (i64.shr_u (local.get $uuid) (i64.const 32)))
+      (local.get $uuid_len))
+    (i64.or
+      (i64.shl (i64.extend_i32_u (local.get $p)) (i64.const 32))
+      (i64.extend_i32_u (i32.sub (i32.const 200) (local.get $p))))))
+"#;
+
+    fn write_wat_module(dir: &std::path::Path, name: &str, wat_src: &str) -> String {
+        let path = dir.join(name);
+        let bytes = wat::parse_str(wat_src).expect("valid wat fixture");
+        std::fs::write(&path, bytes).expect("write wasm fixture");
+        path.to_string_lossy().to_string()
+    }
+
     #[test]
-    fn runtime_rejects_invalid_manifest() {
-        let runtime = PluginRuntime::new();
-        assert!(runtime.load_manifest(PluginManifest::default()).is_err());
+    fn call_wasm_json_round_trips_through_guest_memory() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "echo.wasm", ECHO_WAT);
+        let input = r#"{"tool":"echo","args":{"text":"héllo"}}"#;
+
+        let output = call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, input, TEST_LIMITS)
+            .expect("echo call");
+        assert_eq!(output, input);
     }
 
     #[test]
-    fn runtime_loads_plugin_manifest_files() {
+    fn memory_limit_traps_growth_past_the_cap() {
         let dir = TempDir::new().expect("temp dir");
-        write_manifest(dir.path(), "demo", "demo-provider", "demo_tool");
+        let module = write_wat_module(dir.path(), "grow.wasm", GROW_WAT);
 
-        let runtime = PluginRuntime::new();
+        let output = call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS)
+            .expect("growth within the limit succeeds");
+        assert_eq!(output, "{}");
+
+        let two_pages = PluginExecutionLimits {
+            memory_limit_bytes: 2 * 64 * 1024,
+            ..TEST_LIMITS
+        };
+        let err = call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", two_pages).unwrap_err();
+        assert!(format!("{err:#}").contains("zeroclaw_tool_execute"));
+    }
+
+    #[test]
+    fn memory_limit_rejects_oversized_initial_memory() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "grow.wasm", GROW_WAT);
+        let half_page = PluginExecutionLimits {
+            memory_limit_bytes: 32 * 1024,
+            ..TEST_LIMITS
+        };
+        let err = call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", half_page).unwrap_err();
+        assert!(format!("{err:#}").contains("failed to instantiate"));
+    }
+
+    #[test]
+    fn timeout_interrupts_a_spinning_guest() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "spin.wasm", SPIN_WAT);
+        let limits = PluginExecutionLimits {
+            invoke_timeout_ms: 50,
+            ..TEST_LIMITS
+        };
+
+        let started = std::time::Instant::now();
+        let err = call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", limits).unwrap_err();
+        assert!(err.to_string().contains("timed out after 50ms"), "{err:#}");
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn timeout_does_not_affect_fast_calls() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "echo.wasm", ECHO_WAT);
+        let limits = PluginExecutionLimits {
+            invoke_timeout_ms: 50,
+            ..TEST_LIMITS
+        };
+        let output =
+            call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", limits).expect("fast call");
+        assert_eq!(output, "{}");
+    }
+
+    #[test]
+    fn gzip_compressed_module_runs_like_the_original() {
+        use std::io::Write;
+
+        let dir = TempDir::new().expect("temp dir");
+        let plain = write_wat_module(dir.path(), "echo.wasm", ECHO_WAT);
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(&std::fs::read(&plain).expect("read module"))
+            .expect("compress module");
+        let compressed = dir.path().join("echo.wasm.gz");
+        std::fs::write(&compressed, encoder.finish().expect("finish gzip"))
+            .expect("write compressed module");
+        let compressed = compressed.to_string_lossy().to_string();
+
+        let input = r#"{"tool":"echo","args":{"n":1}}"#;
+        let expected =
+            call_wasm_json("test", &plain, ABI_TOOL_EXEC_FN, input, TEST_LIMITS).expect("plain");
+        let output = call_wasm_json("test", &compressed, ABI_TOOL_EXEC_FN, input, TEST_LIMITS)
+            .expect("gzip module");
+        assert_eq!(output, expected);
+        assert_eq!(
+            read_module_bytes(&compressed).expect("decompress"),
+            std::fs::read(&plain).expect("read module")
+        );
+    }
+
+    #[test]
+    fn corrupt_gzip_module_reports_decompression_error() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = dir.path().join("broken.wasm.gz");
+        let mut raw = GZIP_MAGIC.to_vec();
+        raw.extend_from_slice(b"not really gzip");
+        std::fs::write(&module, raw).expect("write module");
+        let module = module.to_string_lossy().to_string();
+
+        let err = call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS)
+            .expect_err("corrupt module must fail");
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::Decompression {
+                module: module.clone()
+            })
+        );
+        assert!(!err
+            .downcast_ref::<PluginCallError>()
+            .unwrap()
+            .is_transient());
+    }
+
+    /// Whether two handles share one compilation (wasmtime has no `Module` identity API).
+    fn same_module(a: &Module, b: &Module) -> bool {
+        a.image_range() == b.image_range()
+    }
+
+    #[test]
+    fn module_cache_reuses_compiled_module_until_mtime_changes() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "cached.wasm", ECHO_WAT);
+        let engine = shared_engine().expect("engine");
+        let first = cached_module(engine, &module).expect("compile");
+
+        for _ in 0..10 {
+            call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS)
+                .expect("echo call");
+        }
+        let cached = cached_module(engine, &module).expect("cached");
+        assert!(same_module(&first, &cached));
+
+        std::fs::File::options()
+            .write(true)
+            .open(&module)
+            .expect("open module")
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .expect("touch module");
+        let recompiled = cached_module(engine, &module).expect("recompile");
+        assert!(!same_module(&first, &recompiled));
+    }
+
+    #[test]
+    fn instance_pre_is_linked_once_per_compiled_module() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "prelinked.wasm", ECHO_WAT);
+        let has_pre = || {
+            module_cache()
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .get(&module)
+                .is_some_and(|cached| cached.pre.is_some())
+        };
+
+        call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS).expect("echo call");
+        assert!(has_pre(), "the first call links and caches the module");
+
+        std::fs::File::options()
+            .write(true)
+            .open(&module)
+            .expect("open module")
+            .set_modified(SystemTime::now() + Duration::from_secs(60))
+            .expect("touch module");
+        cached_module(shared_engine().expect("engine"), &module).expect("recompile");
+        assert!(!has_pre(), "a recompiled module drops the stale link");
+        call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS).expect("echo call");
+        assert!(has_pre());
+    }
+
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn host_log_forwards_guest_messages_to_tracing() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "log.wasm", LOG_WAT);
+        let logs = CapturedLogs::default();
+        let writer = logs.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_ansi(false)
+            .with_max_level(tracing::Level::TRACE)
+            .with_writer(move || writer.clone())
+            .finish();
+
+        let output = tracing::subscriber::with_default(subscriber, || {
+            call_wasm_json("logger", &module, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS)
+        })
+        .expect("log call");
+        assert_eq!(output, "{}");
+
+        let captured = String::from_utf8(logs.0.lock().unwrap().clone()).expect("utf8 logs");
+        assert!(captured.contains("WARN"), "{captured}");
+        assert!(captured.contains("hello from guest"), "{captured}");
+        assert!(captured.contains("plugin_id=\"logger\""), "{captured}");
+    }
+
+    fn pinned_config(dir: &TempDir, module: &str, sha256: Option<&str>) -> PluginsConfig {
+        std::fs::write(
+            dir.path().join("pinned.plugin.toml"),
+            format!(
+                r#"
+id = "pinned"
+version = "1.0.0"
+module_path = "{}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+
+[[tools]]
+name = "pinned_tool"
+description = "pinned_tool description"
+"#,
+                module.replace('\\', "/")
+            ),
+        )
+        .expect("write manifest");
+        let mut cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        cfg.entries.insert(
+            "pinned".into(),
+            crate::config::PluginEntryConfig {
+                sha256: sha256.map(str::to_string),
+                ..crate::config::PluginEntryConfig::default()
+            },
+        );
+        cfg
+    }
+
+    #[test]
+    fn config_schema_violation_refuses_only_that_plugin() {
+        let dir = TempDir::new().expect("temp dir");
+        std::fs::write(
+            dir.path().join("schema.plugin.toml"),
+            r#"
+id = "schema-demo"
+version = "1.0.0"
+module_path = "plugins/schema.wasm"
+
+[config_schema]
+type = "object"
+required = ["url"]
+"#,
+        )
+        .expect("write manifest");
+        write_manifest(
+            dir.path(),
+            "schemaless",
+            "schemaless-provider",
+            "schemaless_tool",
+        );
+        let mut cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        // Without an entry there are no settings to check.
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("no entry loads");
+        assert!(registry.manifest("schema-demo").is_some());
+        assert!(registry.diagnostics.is_empty());
+
+        cfg.entries.insert(
+            "schema-demo".into(),
+            crate::config::PluginEntryConfig {
+                config: serde_json::json!({ "uri": "https://example.com" }),
+                ..crate::config::PluginEntryConfig::default()
+            },
+        );
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("other plugins still load");
+        assert!(registry.manifest("schema-demo").is_none());
+        assert!(registry.manifest("schemaless").is_some());
+        let [diagnostic] = registry.diagnostics.as_slice() else {
+            panic!("expected one diagnostic: {:?}", registry.diagnostics);
+        };
+        assert_eq!(diagnostic.plugin_id.as_deref(), Some("schema-demo"));
+        assert!(
+            diagnostic.message.contains("'schema-demo'"),
+            "{diagnostic:?}"
+        );
+        assert!(diagnostic.message.contains("'url'"), "{diagnostic:?}");
+
+        cfg.entries.insert(
+            "schema-demo".into(),
+            crate::config::PluginEntryConfig {
+                config: serde_json::json!({ "url": "https://example.com" }),
+                ..crate::config::PluginEntryConfig::default()
+            },
+        );
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("valid settings load");
+        assert_eq!(registry.len(), 2);
+        assert!(registry.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn unsupported_config_schema_refuses_the_plugin_without_an_entry() {
+        let dir = TempDir::new().expect("temp dir");
+        std::fs::write(
+            dir.path().join("bounded.plugin.toml"),
+            r#"
+id = "bounded"
+version = "1.0.0"
+module_path = "plugins/bounded.wasm"
+
+[config_schema]
+type = "object"
+additionalProperties = false
+"#,
+        )
+        .expect("write manifest");
         let cfg = PluginsConfig {
             enabled: true,
             load_paths: vec![dir.path().to_string_lossy().to_string()],
             ..PluginsConfig::default()
         };
-        let reg = runtime
+        let registry = PluginRuntime::new()
             .load_registry_from_config(&cfg)
-            .expect("load registry");
-        assert_eq!(reg.len(), 1);
-        assert_eq!(reg.tools().len(), 1);
-        assert!(reg.has_provider("demo-provider"));
-        assert!(reg.tool_module_path("demo_tool").is_some());
-        assert!(reg.provider_module_path("demo-provider").is_some());
+            .expect("load");
+        assert!(registry.manifest("bounded").is_none());
+        let [diagnostic] = registry.diagnostics.as_slice() else {
+            panic!("expected one diagnostic: {:?}", registry.diagnostics);
+        };
+        assert!(
+            diagnostic.message.contains("'additionalProperties'"),
+            "{diagnostic:?}"
+        );
     }
 
     #[test]
-    fn unpack_ptr_len_roundtrip() {
-        let ptr: u32 = 0x1234_5678;
-        let len: u32 = 0x0000_0100;
-        let packed = ((u64::from(ptr)) << 32) | u64::from(len);
-        let (decoded_ptr, decoded_len) = unpack_ptr_len(packed as i64).expect("unpack");
-        assert_eq!(u32::try_from(decoded_ptr).expect("ptr fits in u32"), ptr);
-        assert_eq!(u32::try_from(decoded_len).expect("len fits in u32"), len);
+    fn sha256_pin_accepts_matching_module() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "pinned.wasm", ECHO_WAT);
+        let digest = hex::encode(Sha256::digest(std::fs::read(&module).expect("module")));
+        let cfg = pinned_config(&dir, &module, Some(&digest.to_uppercase()));
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("matching checksum loads");
+        assert!(registry.tool_module_path("pinned_tool").is_some());
     }
 
     #[test]
-    fn initialize_from_config_applies_updated_plugin_dirs() {
-        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
-        let dir_a = TempDir::new().expect("temp dir a");
-        let dir_b = TempDir::new().expect("temp dir b");
-        write_manifest(
-            dir_a.path(),
-            "reload_a",
-            "reload-provider-a-for-runtime-test",
-            "reload_tool_a",
+    fn sha256_pin_refuses_only_the_mismatched_plugin() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "pinned.wasm", ECHO_WAT);
+        write_manifest(dir.path(), "unpinned", "unpinned-provider", "unpinned_tool");
+        let cfg = pinned_config(&dir, &module, Some(&"0".repeat(64)));
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("other plugins still load");
+
+        assert!(registry.tool_module_path("pinned_tool").is_none());
+        assert!(registry.tool_module_path("unpinned_tool").is_some());
+        let [diagnostic] = registry.diagnostics.as_slice() else {
+            panic!("expected one diagnostic: {:?}", registry.diagnostics);
+        };
+        assert_eq!(diagnostic.plugin_id.as_deref(), Some("pinned"));
+        assert!(
+            diagnostic.message.contains("sha256 mismatch"),
+            "{diagnostic:?}"
         );
-        write_manifest(
-            dir_b.path(),
-            "reload_b",
-            "reload-provider-b-for-runtime-test",
-            "reload_tool_b",
+    }
+
+    #[tokio::test]
+    async fn pinned_module_swap_keeps_the_verified_module_unless_repinned() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "pinned.wasm", ECHO_WAT);
+        let digest = hex::encode(Sha256::digest(std::fs::read(&module).expect("module")));
+        initialize_from_config(&pinned_config(&dir, &module, Some(&digest))).expect("initialize");
+        let args = serde_json::json!({});
+        let call = || execute_plugin_tool("pinned_tool", &args);
+        let echoed = call().await.expect("pinned module runs").output;
+        let swap_in = |wat_src: &str, offset_secs: u64| {
+            write_wat_module(dir.path(), "pinned.wasm", wat_src);
+            std::fs::File::options()
+                .write(true)
+                .open(&module)
+                .expect("open module")
+                .set_modified(SystemTime::now() + Duration::from_secs(offset_secs))
+                .expect("touch module");
+        };
+
+        // Tampered bytes: the reload is refused and calls keep running the
+        // module verified at load.
+        swap_in(ID_CLOCK_WAT, 60);
+        let err = reload_plugin("pinned").unwrap_err();
+        assert!(format!("{err:#}").contains("sha256 mismatch"), "{err:#}");
+        assert_eq!(call().await.expect("verified module").output, echoed);
+        assert_eq!(call().await.expect("verified module").output, echoed);
+
+        // The same swap with a matching pin is accepted.
+        let new_digest = hex::encode(Sha256::digest(std::fs::read(&module).expect("module")));
+        initialize_from_config(&pinned_config(&dir, &module, Some(&new_digest))).expect("re-pin");
+        let output = call().await.expect("re-pinned module runs").output;
+        assert_ne!(output, echoed);
+        assert!(output.split_once(' ').is_some(), "{output}");
+
+        // With no verified module to fall back on, a mismatch is refused.
+        swap_in(ECHO_WAT, 120);
+        evict_module_for_test(&module);
+        let err = call().await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::IntegrityMismatch { module })
         );
+    }
 
-        let cfg_a = PluginsConfig {
+    fn evict_module_for_test(module_path: &str) {
+        module_cache()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .remove(module_path);
+    }
+
+    #[test]
+    fn sha256_pin_is_skipped_when_unset() {
+        let dir = TempDir::new().expect("temp dir");
+        let cfg = pinned_config(&dir, "missing/pinned.wasm", None);
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("no checksum configured");
+        assert!(registry.tool_module_path("pinned_tool").is_some());
+    }
+
+    fn capability_config(dir: &TempDir, allowed: Option<&[&str]>) -> PluginsConfig {
+        std::fs::write(
+            dir.path().join("caps.plugin.toml"),
+            r#"
+id = "caps"
+version = "1.0.0"
+module_path = "plugins/caps.wasm"
+wit_packages = ["zeroclaw:tools@1.0.0", "zeroclaw:hooks@1.0.0"]
+capabilities = ["Tools", "Hooks"]
+
+[[tools]]
+name = "caps_tool"
+description = "caps_tool description"
+"#,
+        )
+        .expect("write manifest");
+        let mut cfg = PluginsConfig {
             enabled: true,
-            load_paths: vec![dir_a.path().to_string_lossy().to_string()],
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
             ..PluginsConfig::default()
         };
-        initialize_from_config(&cfg_a).expect("first initialization should succeed");
-        let reg_a = current_registry();
-        assert!(reg_a.has_provider("reload-provider-a-for-runtime-test"));
+        cfg.entries.insert(
+            "caps".into(),
+            crate::config::PluginEntryConfig {
+                allowed_capabilities: allowed
+                    .map(|names| names.iter().map(|name| (*name).to_string()).collect()),
+                ..crate::config::PluginEntryConfig::default()
+            },
+        );
+        cfg
+    }
 
-        let cfg_b = PluginsConfig {
-            enabled: true,
-            load_paths: vec![dir_b.path().to_string_lossy().to_string()],
-            ..PluginsConfig::default()
+    #[test]
+    fn allowed_capabilities_refuses_only_the_offending_plugin() {
+        let dir = TempDir::new().expect("temp dir");
+        write_manifest(dir.path(), "uncapped", "uncapped-provider", "uncapped_tool");
+        let cfg = capability_config(&dir, Some(&["Tools"]));
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("other plugins still load");
+
+        assert!(registry.tool_module_path("caps_tool").is_none());
+        assert!(registry.tool_module_path("uncapped_tool").is_some());
+        let [diagnostic] = registry.diagnostics.as_slice() else {
+            panic!("expected one diagnostic: {:?}", registry.diagnostics);
         };
-        initialize_from_config(&cfg_b).expect("second initialization should succeed");
-        let reg_b = current_registry();
-        assert!(reg_b.has_provider("reload-provider-b-for-runtime-test"));
-        assert!(!reg_b.has_provider("reload-provider-a-for-runtime-test"));
+        assert_eq!(diagnostic.level, DiagnosticLevel::Error);
+        assert_eq!(diagnostic.plugin_id.as_deref(), Some("caps"));
+        assert!(diagnostic.message.contains("'Hooks'"), "{diagnostic:?}");
     }
 
-    #[tokio::test]
-    async fn timeout_path_releases_semaphore_permit() {
-        let semaphore = Arc::new(Semaphore::new(1));
-        let slow_result =
-            run_blocking_with_timeout(semaphore.clone(), 10, || -> anyhow::Result<&'static str> {
-                std::thread::sleep(std::time::Duration::from_millis(150));
-                Ok("slow")
-            })
-            .await;
-        assert!(slow_result.is_err());
-        assert_eq!(semaphore.available_permits(), 1);
+    #[test]
+    fn allowed_capabilities_accepts_covered_plugin() {
+        let dir = TempDir::new().expect("temp dir");
+        let cfg = capability_config(&dir, Some(&["tools", "hooks"]));
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("all capabilities allowed");
+        assert!(registry.tool_module_path("caps_tool").is_some());
 
-        let fast_result =
-            run_blocking_with_timeout(semaphore, 50, || -> anyhow::Result<&'static str> {
-                Ok("fast")
-            })
-            .await
-            .expect("fast run should succeed");
-        assert_eq!(fast_result, "fast");
+        let unrestricted = capability_config(&dir, None);
+        assert!(PluginRuntime::new()
+            .load_registry_from_config(&unrestricted)
+            .is_ok());
+    }
+
+    #[test]
+    fn call_wasm_json_requires_alloc_dealloc_and_memory() {
+        let dir = TempDir::new().expect("temp dir");
+        let no_alloc = write_wat_module(
+            dir.path(),
+            "no_alloc.wasm",
+            r#"(module (memory (export "memory") 1) (func (export "dealloc") (param i32 i32)))"#,
+        );
+        let err =
+            call_wasm_json("test", &no_alloc, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS).unwrap_err();
+        assert!(format!("{err:#}").contains("missing 'alloc'"));
+
+        let no_memory = write_wat_module(
+            dir.path(),
+            "no_memory.wasm",
+            r#"(module (func (export "alloc") (param i32) (result i32) i32.const 0))"#,
+        );
+        let err =
+            call_wasm_json("test", &no_memory, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::MissingExport {
+                module: no_memory.clone(),
+                export: "memory".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn plugin_call_errors_are_classified() {
+        let dir = TempDir::new().expect("temp dir");
+        let classify = |module: &str, limits: PluginExecutionLimits| {
+            call_wasm_json("test", module, ABI_TOOL_EXEC_FN, "{}", limits)
+                .unwrap_err()
+                .downcast_ref::<PluginCallError>()
+                .cloned()
+                .expect("typed plugin error")
+        };
+
+        let spin = write_wat_module(dir.path(), "spin.wasm", SPIN_WAT);
+        let timed_out = classify(
+            &spin,
+            PluginExecutionLimits {
+                invoke_timeout_ms: 20,
+                ..TEST_LIMITS
+            },
+        );
+        assert_eq!(timed_out, PluginCallError::Timeout { timeout_ms: 20 });
+        assert!(timed_out.is_transient());
+
+        let grow = write_wat_module(dir.path(), "grow.wasm", GROW_WAT);
+        let trapped = classify(
+            &grow,
+            PluginExecutionLimits {
+                memory_limit_bytes: 2 * 64 * 1024,
+                ..TEST_LIMITS
+            },
+        );
+        assert_eq!(
+            trapped,
+            PluginCallError::Trap {
+                function: ABI_TOOL_EXEC_FN.to_string(),
+            }
+        );
+        assert!(!trapped.is_transient());
+
+        let missing = dir.path().join("absent.wasm").to_string_lossy().to_string();
+        let unreadable = classify(&missing, TEST_LIMITS);
+        assert!(matches!(unreadable, PluginCallError::Io { .. }));
+        assert!(unreadable.is_transient());
+
+        let garbage = dir.path().join("garbage.wasm");
+        std::fs::write(&garbage, b"not wasm").expect("write garbage");
+        let invalid = classify(&garbage.to_string_lossy(), TEST_LIMITS);
+        assert!(matches!(invalid, PluginCallError::InvalidModule { .. }));
+        assert!(!invalid.is_transient());
+
+        let oversized = "x".repeat(MAX_WASM_PAYLOAD_BYTES_FALLBACK + 1);
+        let err =
+            call_wasm_json("test", &spin, ABI_TOOL_EXEC_FN, &oversized, TEST_LIMITS).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(PluginCallError::Serialization(_))
+        ));
+    }
+
+    /// Tool export skeleton for the sandbox escape fixtures: `$imports`,
+    /// `$memory` and `$body` are spliced in, and `$body` must leave the packed
+    /// output on the stack.
+    fn escape_wat(imports: &str, memory: &str, body: &str) -> String {
+        format!(
+            r#"
+(module
+  {imports}
+  (memory (export "memory") {memory})
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc") (param i32 i32))
+  (func (export "zeroclaw_tool_execute") (param i32 i32) (result i64)
+    {body}))
+"#
+        )
+    }
+
+    #[test]
+    fn sandbox_escape_attempts_are_blocked() {
+        let dir = TempDir::new().expect("temp dir");
+        let limits = PluginExecutionLimits {
+            invoke_timeout_ms: 50,
+            memory_limit_bytes: 4 * 64 * 1024,
+        };
+        let cases = [
+            (
+                "file read without filesystem capability",
+                escape_wat(
+                    r#"(import "wasi_snapshot_preview1" "path_open"
+                      (func $path_open (param i32 i32 i32 i32 i32 i64 i64 i32 i32) (result i32)))"#,
+                    "1",
+                    "(drop (call $path_open (i32.const 3) (i32.const 0) (i32.const 0) (i32.const 11)
+                       (i32.const 0) (i64.const 2) (i64.const 0) (i32.const 0) (i32.const 64)))
+                     i64.const 0",
+                ),
+                PluginCallError::Instantiation {
+                    module: String::new(),
+                },
+            ),
+            (
+                "file read through an unknown host import",
+                escape_wat(
+                    r#"(import "env" "read_file" (func $read_file (param i32 i32) (result i64)))"#,
+                    "1",
+                    "(call $read_file (i32.const 0) (i32.const 11))",
+                ),
+                PluginCallError::Instantiation {
+                    module: String::new(),
+                },
+            ),
+            (
+                "network call without network capability",
+                escape_wat(
+                    r#"(import "wasi_snapshot_preview1" "sock_accept"
+                      (func $sock_accept (param i32 i32 i32) (result i32)))"#,
+                    "1",
+                    "(drop (call $sock_accept (i32.const 3) (i32.const 0) (i32.const 64)))
+                     i64.const 0",
+                ),
+                PluginCallError::Instantiation {
+                    module: String::new(),
+                },
+            ),
+            (
+                "network call through an unknown host import",
+                escape_wat(
+                    r#"(import "env" "http_request" (func $http (param i32 i32) (result i64)))"#,
+                    "1",
+                    "(call $http (i32.const 0) (i32.const 0))",
+                ),
+                PluginCallError::Instantiation {
+                    module: String::new(),
+                },
+            ),
+            (
+                "infinite loop",
+                escape_wat("", "1", "(loop $spin (br $spin)) i64.const 0"),
+                PluginCallError::Timeout {
+                    timeout_ms: limits.invoke_timeout_ms,
+                },
+            ),
+            (
+                "unbounded memory growth",
+                escape_wat(
+                    "",
+                    "1",
+                    "(loop $grow
+                       (br_if $grow (i32.ne (memory.grow (i32.const 1)) (i32.const -1))))
+                     i64.const 0",
+                ),
+                PluginCallError::Trap {
+                    function: ABI_TOOL_EXEC_FN.to_string(),
+                },
+            ),
+            (
+                "initial memory above the limit",
+                escape_wat("", "64", "i64.const 0"),
+                PluginCallError::Instantiation {
+                    module: String::new(),
+                },
+            ),
+            (
+                "oversized response",
+                escape_wat(
+                    "",
+                    "1",
+                    &format!("i64.const {}", MAX_WASM_PAYLOAD_BYTES_FALLBACK + 1),
+                ),
+                PluginCallError::Serialization(String::new()),
+            ),
+            (
+                "response outside guest memory",
+                escape_wat("", "1", "i64.const 0x0000fff000001000"),
+                PluginCallError::Serialization(String::new()),
+            ),
+            (
+                "negative packed response",
+                escape_wat("", "1", "i64.const -1"),
+                PluginCallError::Serialization(String::new()),
+            ),
+        ];
+
+        for (index, (name, wat_src, expected)) in cases.into_iter().enumerate() {
+            let module = write_wat_module(dir.path(), &format!("escape-{index}.wasm"), &wat_src);
+            let result = call_wasm_json("escape", &module, ABI_TOOL_EXEC_FN, "{}", limits);
+            let err = match result {
+                Ok(output) => panic!("{name}: escaped the sandbox with output {output:?}"),
+                Err(err) => err,
+            };
+            let actual = err
+                .downcast_ref::<PluginCallError>()
+                .cloned()
+                .unwrap_or_else(|| panic!("{name}: untyped error {err:#}"));
+            let matched = match (&expected, &actual) {
+                (PluginCallError::Instantiation { .. }, PluginCallError::Instantiation { .. })
+                | (PluginCallError::Serialization(_), PluginCallError::Serialization(_)) => true,
+                _ => expected == actual,
+            };
+            assert!(matched, "{name}: expected {expected:?}, got {actual:?}");
+        }
+    }
+
+    #[test]
+    fn runtime_rejects_invalid_manifest() {
+        let runtime = PluginRuntime::new();
+        assert!(runtime.load_manifest(PluginManifest::default()).is_err());
+    }
+
+    #[test]
+    fn runtime_loads_plugin_manifest_files() {
+        let dir = TempDir::new().expect("temp dir");
+        write_manifest(dir.path(), "demo", "demo-provider", "demo_tool");
+
+        let runtime = PluginRuntime::new();
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        let reg = runtime
+            .load_registry_from_config(&cfg)
+            .expect("load registry");
+        assert_eq!(reg.len(), 1);
+        assert_eq!(reg.tools().len(), 1);
+        assert!(reg.has_provider("demo-provider"));
+        assert!(reg.tool_module_path("demo_tool").is_some());
+        assert!(reg.provider_module_path("demo-provider").is_some());
+    }
+
+    #[test]
+    fn runtime_skips_manifests_beyond_max_plugins() {
+        let dir = TempDir::new().expect("temp dir");
+        for id in ["cap_a", "cap_b", "cap_c"] {
+            write_manifest(
+                dir.path(),
+                id,
+                &format!("{id}-provider"),
+                &format!("{id}_tool"),
+            );
+        }
+
+        let runtime = PluginRuntime::new();
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            max_plugins: 2,
+            ..PluginsConfig::default()
+        };
+        let reg = runtime
+            .load_registry_from_config(&cfg)
+            .expect("load registry");
+        assert_eq!(reg.len(), 2);
+        assert!(reg.manifest("cap_a").is_some());
+        assert!(reg.manifest("cap_b").is_some());
+        assert!(reg.manifest("cap_c").is_none());
+    }
+
+    #[test]
+    fn unpack_ptr_len_roundtrip() {
+        let ptr: u32 = 0x1234_5678;
+        let len: u32 = 0x0000_0100;
+        let packed = ((u64::from(ptr)) << 32) | u64::from(len);
+        let (decoded_ptr, decoded_len) = unpack_ptr_len(packed as i64).expect("unpack");
+        assert_eq!(u32::try_from(decoded_ptr).expect("ptr fits in u32"), ptr);
+        assert_eq!(u32::try_from(decoded_len).expect("len fits in u32"), len);
+    }
+
+    #[test]
+    fn initialize_from_config_applies_updated_plugin_dirs() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir_a = TempDir::new().expect("temp dir a");
+        let dir_b = TempDir::new().expect("temp dir b");
+        write_manifest(
+            dir_a.path(),
+            "reload_a",
+            "reload-provider-a-for-runtime-test",
+            "reload_tool_a",
+        );
+        write_manifest(
+            dir_b.path(),
+            "reload_b",
+            "reload-provider-b-for-runtime-test",
+            "reload_tool_b",
+        );
+
+        let cfg_a = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir_a.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        initialize_from_config(&cfg_a).expect("first initialization should succeed");
+        let reg_a = current_registry();
+        assert!(reg_a.has_provider("reload-provider-a-for-runtime-test"));
+
+        let cfg_b = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir_b.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        initialize_from_config(&cfg_b).expect("second initialization should succeed");
+        let reg_b = current_registry();
+        assert!(reg_b.has_provider("reload-provider-b-for-runtime-test"));
+        assert!(!reg_b.has_provider("reload-provider-a-for-runtime-test"));
+    }
+
+    #[test]
+    fn reload_plugin_picks_up_changed_manifest_and_module() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "reloadable.wasm", ECHO_WAT);
+        let write_versioned = |version: &str| {
+            std::fs::write(
+                dir.path().join("reloadable.plugin.toml"),
+                format!(
+                    r#"
+id = "reloadable-for-runtime-test"
+version = "{version}"
+module_path = "{module}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+
+[[tools]]
+name = "reloadable_tool_for_runtime_test"
+description = "reloadable tool"
+"#
+                ),
+            )
+            .expect("write manifest");
+        };
+        write_versioned("1.0.0");
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        initialize_from_config(&cfg).expect("initialize");
+        let before = cached_module(shared_engine().expect("engine"), &module).expect("compile");
+
+        write_versioned("2.0.0");
+        let summary = reload_plugin("reloadable-for-runtime-test").expect("reload");
+        assert_eq!(summary.updated, vec!["reloadable-for-runtime-test"]);
+        let registry = current_registry();
+        let manifest = registry
+            .manifest("reloadable-for-runtime-test")
+            .expect("registered");
+        assert_eq!(manifest.version.as_deref(), Some("2.0.0"));
+
+        let after = cached_module(shared_engine().expect("engine"), &module).expect("cached");
+        assert!(!same_module(&before, &after));
+
+        // An unknown id fails before the pending manifest change is applied.
+        write_versioned("3.0.0");
+        let generation = current_registry().generation();
+        assert!(reload_plugin("missing-plugin-for-runtime-test").is_err());
+        let registry = current_registry();
+        assert_eq!(registry.generation(), generation);
+        assert_eq!(
+            registry
+                .manifest("reloadable-for-runtime-test")
+                .and_then(|manifest| manifest.version.as_deref()),
+            Some("2.0.0")
+        );
+    }
+
+    #[test]
+    fn failed_hot_reload_keeps_registry_and_records_fingerprints() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        write_manifest(
+            dir.path(),
+            "hot_reload_ok",
+            "hot-reload-provider-for-runtime-test",
+            "hot_reload_tool",
+        );
+        let cfg = PluginsConfig {
+            enabled: true,
+            hot_reload: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        initialize_from_config(&cfg).expect("initialize");
+        let generation = current_registry().generation();
+
+        std::fs::write(dir.path().join("broken.plugin.toml"), "id = [").expect("write manifest");
+        let registry = current_registry();
+        assert_eq!(registry.generation(), generation);
+        assert!(registry.has_provider("hot-reload-provider-for-runtime-test"));
+
+        let recorded = registry_cell()
+            .read()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .fingerprints
+            .clone();
+        assert_eq!(recorded, collect_manifest_fingerprints(&cfg.load_paths));
+    }
+
+    #[tokio::test]
+    async fn plugin_state_persists_across_calls_and_reloads() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let state_dir = TempDir::new().expect("state dir");
+        let module = write_wat_module(dir.path(), "counter.wasm", COUNTER_WAT);
+        // Two plugins backed by the same module.
+        for id in ["counter", "other-counter"] {
+            std::fs::write(
+                dir.path().join(format!("{id}.plugin.toml")),
+                format!(
+                    r#"
+id = "{id}"
+version = "1.0.0"
+module_path = "{module}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+
+[[tools]]
+name = "{id}_tool_for_runtime_test"
+description = "counts its calls"
+"#
+                ),
+            )
+            .expect("write manifest");
+        }
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            state_dir: state_dir.path().to_string_lossy().to_string(),
+            ..PluginsConfig::default()
+        };
+        initialize_from_config(&cfg).expect("initialize");
+        async fn call(plugin_id: &str) -> String {
+            execute_plugin_tool(
+                &format!("{plugin_id}_tool_for_runtime_test"),
+                &serde_json::json!({}),
+            )
+            .await
+            .expect("counter call")
+            .output
+        }
+
+        assert_eq!(call("counter").await, "1");
+        assert_eq!(call("counter").await, "2");
+        reload_plugin("counter").expect("reload");
+        assert_eq!(call("counter").await, "3");
+        // Same module, different plugin: its own namespace starts from scratch.
+        assert_eq!(call("other-counter").await, "1");
+        assert_eq!(call("counter").await, "4");
+
+        for (id, count) in [("counter", "4"), ("other-counter", "1")] {
+            let saved =
+                load_plugin_state(&state_dir.path().join(format!("{id}.json"))).expect("state");
+            assert_eq!(saved.get("count").map(String::as_str), Some(count), "{id}");
+        }
+    }
+
+    #[test]
+    fn plugin_state_is_namespaced_and_bounded() {
+        for unusable in ["", "../escape", "a/b", ".hidden", "plugins/demo.wasm"] {
+            assert_eq!(plugin_state_path(unusable), None, "{unusable:?}");
+        }
+        let path = plugin_state_path("demo-plugin_1.0").expect("usable id");
+        assert_eq!(
+            path.file_name().and_then(std::ffi::OsStr::to_str),
+            Some("demo-plugin_1.0.json")
+        );
+
+        let dir = TempDir::new().expect("temp dir");
+        let path = dir.path().join("demo.json");
+        let mut state = BTreeMap::from([("small".to_string(), "1".to_string())]);
+        save_plugin_state(&path, &state).expect("small state fits");
+        state.insert("big".to_string(), "x".repeat(MAX_PLUGIN_STATE_BYTES));
+        assert!(save_plugin_state(&path, &state).is_err());
+        assert_eq!(load_plugin_state(&path).expect("state").len(), 1);
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+            .expect("read state dir")
+            .flatten()
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(leftovers, ["demo.json"], "no temp files are left behind");
+
+        assert!(Arc::ptr_eq(
+            &plugin_state_lock("demo"),
+            &plugin_state_lock("demo")
+        ));
+        assert!(!Arc::ptr_eq(
+            &plugin_state_lock("demo"),
+            &plugin_state_lock("other")
+        ));
+    }
+
+    #[test]
+    fn plugin_state_lock_is_reentrant_on_its_thread_and_excludes_others() {
+        let (entered_tx, entered_rx) = std::sync::mpsc::channel();
+        let (nested, other) = with_plugin_state_lock("reentrant", || {
+            let other = std::thread::spawn(move || {
+                with_plugin_state_lock("reentrant", || entered_tx.send(()).expect("send"));
+            });
+            assert!(
+                entered_rx
+                    .recv_timeout(std::time::Duration::from_millis(100))
+                    .is_err(),
+                "another thread must wait for the held lock"
+            );
+            let nested = with_plugin_state_lock("reentrant", || 7);
+            (nested, other)
+        });
+        assert_eq!(nested, 7);
+        other.join().expect("other thread");
+        entered_rx
+            .recv()
+            .expect("other thread entered after release");
+    }
+
+    #[test]
+    fn host_uuid_and_clock_are_well_formed() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "id-clock.wasm", ID_CLOCK_WAT);
+        let call = || {
+            let before = chrono::Utc::now().timestamp_millis();
+            let output = call_wasm_json("test", &module, ABI_TOOL_EXEC_FN, "{}", TEST_LIMITS)
+                .expect("id/clock call");
+            let after = chrono::Utc::now().timestamp_millis();
+            let (id, millis) = output.split_once(' ').expect("uuid and millis");
+            let millis: i64 = millis.parse().expect("decimal millis");
+            assert!(
+                (before..=after).contains(&millis),
+                "{millis} outside host clock"
+            );
+            let parsed = uuid::Uuid::parse_str(id).expect("valid uuid");
+            assert_eq!(parsed.get_version_num(), 4);
+            (id.to_string(), millis)
+        };
+
+        let (first_id, first_ms) = call();
+        let (second_id, second_ms) = call();
+        assert_eq!(first_id.len(), 36);
+        assert_ne!(first_id, second_id);
+        assert!(second_ms >= first_ms);
+    }
+
+    #[test]
+    fn validate_registry_reports_missing_exports_and_bad_modules() {
+        let dir = TempDir::new().expect("temp dir");
+        let complete = write_wat_module(dir.path(), "complete.wasm", ECHO_WAT);
+        let no_provider = write_wat_module(dir.path(), "tool_only.wasm", ECHO_WAT);
+        let not_wasm = dir.path().join("not_wasm.wasm");
+        std::fs::write(&not_wasm, b"plain text").expect("write not wasm");
+        let not_wasm = not_wasm.to_string_lossy().to_string();
+        for (id, module_path, providers) in [
+            ("complete-for-validate-test", complete.as_str(), "[]"),
+            (
+                "no-provider-export-for-validate-test",
+                no_provider.as_str(),
+                r#"["validate-provider"]"#,
+            ),
+            ("not-wasm-for-validate-test", not_wasm.as_str(), "[]"),
+        ] {
+            std::fs::write(
+                dir.path().join(format!("{id}.plugin.toml")),
+                format!(
+                    r#"
+id = "{id}"
+version = "1.0.0"
+module_path = "{module_path}"
+wit_packages = ["zeroclaw:tools@1.0.0", "zeroclaw:providers@1.0.0"]
+providers = {providers}
+
+[[tools]]
+name = "{id}-tool"
+description = "validate tool"
+"#
+                ),
+            )
+            .expect("write manifest");
+        }
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("load registry");
+        let report = validate_registry(&registry);
+        assert_eq!(report.len(), 3);
+
+        assert!(report[0].passed(), "{:?}", report[0]);
+        assert_eq!(report[1].id, "no-provider-export-for-validate-test");
+        assert_eq!(report[1].missing_exports, vec![ABI_PROVIDER_CHAT_FN]);
+        assert!(report[1].errors.is_empty());
+        assert_eq!(report[2].id, "not-wasm-for-validate-test");
+        assert!(report[2].errors[0].contains("bad magic"), "{:?}", report[2]);
+    }
+
+    #[tokio::test]
+    async fn health_report_distinguishes_healthy_and_missing_modules() {
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "healthy.wasm", ECHO_WAT);
+        for (id, module_path) in [
+            ("healthy-for-health-test", module.as_str()),
+            ("missing-for-health-test", "missing/nowhere.wasm"),
+            ("disabled-for-health-test", module.as_str()),
+        ] {
+            std::fs::write(
+                dir.path().join(format!("{id}.plugin.toml")),
+                format!(
+                    r#"
+id = "{id}"
+version = "1.0.0"
+module_path = "{module_path}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+"#
+                ),
+            )
+            .expect("write manifest");
+        }
+        let mut cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        cfg.entries.insert(
+            "disabled-for-health-test".to_string(),
+            crate::config::PluginEntryConfig {
+                enabled: Some(false),
+                ..crate::config::PluginEntryConfig::default()
+            },
+        );
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("load registry");
+        let report = check_registry_health(&registry, TEST_LIMITS).await;
+        let ids: Vec<&str> = report.iter().map(|health| health.id.as_str()).collect();
+        assert_eq!(
+            ids,
+            vec![
+                "disabled-for-health-test",
+                "healthy-for-health-test",
+                "missing-for-health-test"
+            ]
+        );
+
+        let healthy = report
+            .iter()
+            .find(|health| health.id == "healthy-for-health-test")
+            .expect("healthy plugin reported");
+        assert!(healthy.enabled);
+        assert!(healthy.module_exists);
+        assert_eq!(healthy.healthy, Some(true));
+        assert_eq!(healthy.last_error, None);
+
+        let missing = report
+            .iter()
+            .find(|health| health.id == "missing-for-health-test")
+            .expect("missing plugin reported");
+        assert!(missing.enabled);
+        assert!(!missing.module_exists);
+        assert_eq!(missing.healthy, None);
+        assert!(missing.last_error.is_some());
+
+        let disabled = &report[0];
+        assert!(!disabled.enabled);
+        assert!(disabled.module_exists);
+        assert_eq!(disabled.healthy, None);
+        assert_eq!(disabled.last_error, None);
+    }
+
+    #[tokio::test]
+    async fn health_report_runs_no_guest_code_and_ignores_busy_plugins() {
+        let dir = TempDir::new().expect("temp dir");
+        // Instantiating this module would trap in its start function.
+        let trapping_start = ECHO_WAT.replacen(
+            "(module",
+            "(module\n  (func $start unreachable)\n  (start $start)",
+            1,
+        );
+        let module = write_wat_module(dir.path(), "start-trap.wasm", &trapping_start);
+        std::fs::write(
+            dir.path().join("busy-for-health-test.plugin.toml"),
+            format!(
+                r#"
+id = "busy-for-health-test"
+version = "1.0.0"
+module_path = "{module}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+"#
+            ),
+        )
+        .expect("write manifest");
+        let mut cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        cfg.entries.insert(
+            "busy-for-health-test".to_string(),
+            crate::config::PluginEntryConfig {
+                max_concurrent_calls: Some(1),
+                timeout_ms: Some(200),
+                ..crate::config::PluginEntryConfig::default()
+            },
+        );
+        let registry = PluginRuntime::new()
+            .load_registry_from_config(&cfg)
+            .expect("load registry");
+        // Every call permit is taken, as by a long-running call.
+        let _busy = plugin_semaphore("busy-for-health-test", 1)
+            .acquire_owned()
+            .await
+            .expect("permit");
+
+        let report = check_registry_health(&registry, TEST_LIMITS).await;
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].healthy, Some(true), "{:?}", report[0].last_error);
+    }
+
+    #[tokio::test]
+    async fn per_plugin_limit_serializes_calls_independently_of_global_limit() {
+        async fn max_parallelism(plugin_limiter: Option<Arc<Semaphore>>) -> usize {
+            let global = Arc::new(Semaphore::new(8));
+            let active = Arc::new(AtomicUsize::new(0));
+            let peak = Arc::new(AtomicUsize::new(0));
+            let calls = (0..3).map(|_| {
+                let active = Arc::clone(&active);
+                let peak = Arc::clone(&peak);
+                run_plugin_call(
+                    plugin_limiter.clone(),
+                    Arc::new(AtomicUsize::new(0)),
+                    Arc::clone(&global),
+                    1_000,
+                    move || {
+                        let now = active.fetch_add(1, Ordering::SeqCst) + 1;
+                        peak.fetch_max(now, Ordering::SeqCst);
+                        std::thread::sleep(std::time::Duration::from_millis(50));
+                        active.fetch_sub(1, Ordering::SeqCst);
+                        Ok(())
+                    },
+                )
+            });
+            for result in futures_util::future::join_all(calls).await {
+                result.expect("call should succeed");
+            }
+            peak.load(Ordering::SeqCst)
+        }
+
+        assert_eq!(max_parallelism(Some(Arc::new(Semaphore::new(1)))).await, 1);
+        assert!(max_parallelism(None).await > 1);
+    }
+
+    #[tokio::test]
+    async fn plugins_sharing_a_module_keep_their_own_limits() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "busy.wasm", &busy_wat(50_000_000));
+        for id in ["serial-shared", "hasty-shared"] {
+            std::fs::write(
+                dir.path().join(format!("{id}.plugin.toml")),
+                format!(
+                    r#"
+id = "{id}"
+version = "1.0.0"
+module_path = "{module}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+
+[[tools]]
+name = "{id}_tool"
+description = "busy tool"
+"#
+                ),
+            )
+            .expect("write manifest");
+        }
+        let mut cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        cfg.entries.insert(
+            "serial-shared".into(),
+            PluginEntryConfig {
+                max_concurrent_calls: Some(1),
+                ..PluginEntryConfig::default()
+            },
+        );
+        cfg.entries.insert(
+            "hasty-shared".into(),
+            PluginEntryConfig {
+                timeout_ms: Some(1),
+                ..PluginEntryConfig::default()
+            },
+        );
+        initialize_from_config(&cfg).expect("initialize");
+
+        let serial_calls = tokio::spawn(futures_util::future::join_all((0..3).map(|_| async {
+            execute_plugin_tool("serial-shared_tool", &serde_json::json!({})).await
+        })));
+        let mut peak = 0;
+        while !serial_calls.is_finished() {
+            let in_flight = plugin_in_flight_calls();
+            peak = peak.max(in_flight.get("serial-shared").copied().unwrap_or_default());
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        for result in serial_calls.await.expect("join") {
+            result.expect("serialized call succeeds");
+        }
+        assert_eq!(peak, 1, "serial-shared calls must not overlap");
+
+        // The second plugin's own timeout applies, not the first's settings.
+        let err = execute_plugin_tool("hasty-shared_tool", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::Timeout { timeout_ms: 1 })
+        );
+    }
+
+    #[tokio::test]
+    async fn swap_module_lets_in_flight_calls_finish_on_the_old_module() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let old = write_wat_module(dir.path(), "slow.wasm", &busy_wat(400_000_000));
+        let new = write_wat_module(dir.path(), "id-clock.wasm", ID_CLOCK_WAT);
+        let no_tool = write_wat_module(
+            dir.path(),
+            "no-tool.wasm",
+            r#"(module
+  (memory (export "memory") 1)
+  (func (export "alloc") (param i32) (result i32) i32.const 1024)
+  (func (export "dealloc") (param i32 i32)))"#,
+        );
+        std::fs::write(
+            dir.path().join("swappable.plugin.toml"),
+            format!(
+                r#"
+id = "swappable"
+version = "1.0.0"
+module_path = "{old}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+
+[[tools]]
+name = "swappable_tool"
+description = "swappable tool"
+"#
+            ),
+        )
+        .expect("write manifest");
+        let mut cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        cfg.entries.insert(
+            "swappable".into(),
+            PluginEntryConfig {
+                timeout_ms: Some(20_000),
+                ..PluginEntryConfig::default()
+            },
+        );
+        initialize_from_config(&cfg).expect("initialize");
+
+        let in_flight = tokio::spawn(async {
+            execute_plugin_tool("swappable_tool", &serde_json::json!({ "n": 1 })).await
+        });
+        timeout(Duration::from_secs(10), async {
+            while plugin_in_flight_calls().get("swappable") != Some(&1) {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("call on the old module starts");
+
+        swap_module("swappable", &new, None, None)
+            .await
+            .expect("swap");
+        let fresh = execute_plugin_tool("swappable_tool", &serde_json::json!({}))
+            .await
+            .expect("call on the new module")
+            .output;
+        let (id, _) = fresh.split_once(' ').expect("new module output");
+        uuid::Uuid::parse_str(id).expect("new module returns a uuid");
+
+        let finished = in_flight.await.expect("join").expect("old call completes");
+        assert!(
+            finished.output.contains(r#""n":1"#),
+            "in-flight call should echo on the old module: {}",
+            finished.output
+        );
+        assert!(!module_cache()
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+            .contains_key(&old));
+
+        // Rejected swaps leave routing on the current module.
+        let err = swap_module("swappable", &no_tool, None, None)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains(ABI_TOOL_EXEC_FN), "{err:#}");
+        let err = swap_module("swappable", &old, Some(&"0".repeat(64)), None)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::IntegrityMismatch {
+                module: old.clone()
+            })
+        );
+        assert_eq!(
+            current_registry().tool_module_path("swappable_tool"),
+            Some(new.as_str())
+        );
+    }
+
+    #[tokio::test]
+    async fn swap_module_migrates_state_audits_and_survives_reloads() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let state_dir = TempDir::new().expect("state dir");
+        let audit_dir = TempDir::new().expect("audit dir");
+        let old = write_wat_module(dir.path(), "old.wasm", ECHO_WAT);
+        let manifest_upgrade = write_wat_module(dir.path(), "upgrade.wasm", LOG_WAT);
+        let refusing = write_wat_module(
+            dir.path(),
+            "refusing.wasm",
+            &migrate_wat(r#"{"error":"schema too new"}"#),
+        );
+        let migrating = write_wat_module(dir.path(), "migrating.wasm", &migrate_wat("{}"));
+        let write_plugin_manifest = |module: &str| {
+            std::fs::write(
+                dir.path().join("migrating.plugin.toml"),
+                format!(
+                    r#"
+id = "migrating"
+version = "1.0.0"
+module_path = "{module}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+
+[[tools]]
+name = "migrating_tool"
+description = "migrating tool"
+"#
+                ),
+            )
+            .expect("write manifest");
+        };
+        write_plugin_manifest(&old);
+        let cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            state_dir: state_dir.path().to_string_lossy().to_string(),
+            ..PluginsConfig::default()
+        };
+        initialize_from_config(&cfg).expect("initialize");
+        let audit = AuditLogger::new(
+            crate::config::AuditConfig::default(),
+            audit_dir.path().to_path_buf(),
+        )
+        .expect("audit logger");
+        let state_file = state_dir.path().join("migrating.json");
+
+        let err = swap_module("migrating", &refusing, None, Some(&audit))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("schema too new"), "{err:#}");
+        assert_eq!(
+            current_registry().tool_module_path("migrating_tool"),
+            Some(old.as_str())
+        );
+
+        swap_module("migrating", &migrating, None, Some(&audit))
+            .await
+            .expect("swap");
+        let saved = load_plugin_state(&state_file).expect("state");
+        assert_eq!(saved.get("schema").map(String::as_str), Some("2"));
+
+        // Reloads keep the swapped module while the manifest is unchanged...
+        reload_plugin("migrating").expect("reload");
+        assert_eq!(
+            current_registry().tool_module_path("migrating_tool"),
+            Some(migrating.as_str())
+        );
+        // ...and give way once the manifest points somewhere new.
+        write_plugin_manifest(&manifest_upgrade);
+        reload_plugin("migrating").expect("reload after manifest change");
+        assert_eq!(
+            current_registry().tool_module_path("migrating_tool"),
+            Some(manifest_upgrade.as_str())
+        );
+
+        let log = std::fs::read_to_string(audit_dir.path().join("audit.log")).expect("audit log");
+        let events: Vec<AuditEvent> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).expect("audit event"))
+            .collect();
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|event| matches!(event.event_type, AuditEventType::ConfigChange)));
+        let outcomes: Vec<bool> = events
+            .iter()
+            .map(|event| event.result.as_ref().expect("result").success)
+            .collect();
+        assert_eq!(outcomes, [false, true]);
+    }
+
+    #[tokio::test]
+    async fn concurrent_wasm_calls_complete_off_the_async_runtime() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "concurrent.wasm", ECHO_WAT);
+        let calls = (0..32).map(|i| {
+            call_wasm_json_limited(
+                "concurrent".to_string(),
+                module.clone(),
+                PluginSettings {
+                    max_concurrent_calls: Some(4),
+                    ..PluginSettings::default()
+                },
+                CallPolicy::default(),
+                ABI_TOOL_EXEC_FN,
+                format!(r#"{{"call":{i}}}"#),
+            )
+        });
+        let results = timeout(
+            Duration::from_secs(10),
+            futures_util::future::join_all(calls),
+        )
+        .await
+        .expect("concurrent calls should not deadlock");
+        for (i, result) in results.into_iter().enumerate() {
+            assert_eq!(result.expect("echo call"), format!(r#"{{"call":{i}}}"#));
+        }
+        assert_eq!(plugin_in_flight_calls().get("concurrent"), Some(&0));
+    }
+
+    #[derive(Default)]
+    struct PayloadSizeObserver {
+        sizes: Mutex<Vec<(String, u64)>>,
+    }
+
+    impl Observer for PayloadSizeObserver {
+        fn record_event(&self, _event: &crate::observability::ObserverEvent) {}
+
+        fn record_metric(&self, metric: &ObserverMetric) {
+            if let ObserverMetric::PluginPayloadBytes {
+                plugin,
+                operation,
+                direction,
+                bytes,
+            } = metric
+            {
+                if plugin == "payload-sizes" {
+                    assert_eq!(operation, ABI_TOOL_EXEC_FN);
+                    self.sizes.lock().unwrap().push((direction.clone(), *bytes));
+                }
+            }
+        }
+
+        fn name(&self) -> &str {
+            "payload-sizes"
+        }
+
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[tokio::test]
+    async fn plugin_calls_record_request_and_response_sizes() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "sizes.wasm", ECHO_WAT);
+        let capture = Arc::new(PayloadSizeObserver::default());
+        let observer: Arc<dyn Observer> = capture.clone();
+        register_observer(&observer);
+        register_observer(&observer);
+        for len in [10, 1000] {
+            let payload = format!(r#"{{"text":"{}"}}"#, "x".repeat(len));
+            call_wasm_json_limited(
+                "payload-sizes".to_string(),
+                module.clone(),
+                PluginSettings::default(),
+                CallPolicy::default(),
+                ABI_TOOL_EXEC_FN,
+                payload,
+            )
+            .await
+            .expect("echo call");
+        }
+
+        assert_eq!(
+            *capture.sizes.lock().unwrap(),
+            [
+                ("request".to_string(), 21),
+                ("response".to_string(), 21),
+                ("request".to_string(), 1011),
+                ("response".to_string(), 1011)
+            ],
+            "each size is recorded once on the registered observer"
+        );
+    }
+
+    #[tokio::test]
+    async fn retry_transient_retries_only_transient_failures() {
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let policy = RetryPolicy {
+            attempts: 3,
+            backoff_ms: 1,
+            retry_timeouts: true,
+        };
+        let calls = AtomicU32::new(0);
+        let counter = &calls;
+        let result = retry_transient(policy, move || async move {
+            if counter.fetch_add(1, Ordering::SeqCst) < 2 {
+                Err(PluginCallError::Timeout { timeout_ms: 10 }.into())
+            } else {
+                Ok("third time lucky")
+            }
+        })
+        .await
+        .expect("succeeds after two transient failures");
+        assert_eq!(result, "third time lucky");
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+
+        let calls = AtomicU32::new(0);
+        let counter = &calls;
+        let err = retry_transient(policy, move || async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(PluginCallError::Serialization("bad json".into()).into())
+        })
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("bad json"));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let calls = AtomicU32::new(0);
+        let counter = &calls;
+        let exhausted = retry_transient(
+            RetryPolicy {
+                attempts: 1,
+                backoff_ms: 1,
+                retry_timeouts: true,
+            },
+            move || async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                Err::<(), _>(PluginCallError::Timeout { timeout_ms: 10 }.into())
+            },
+        )
+        .await;
+        assert!(exhausted.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Without `retry_timeouts`, unreadable modules are still retried but
+        // timeouts are not.
+        let no_timeouts = RetryPolicy {
+            retry_timeouts: false,
+            ..policy
+        };
+        let calls = AtomicU32::new(0);
+        let counter = &calls;
+        let result = retry_transient(no_timeouts, move || async move {
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(PluginCallError::Io {
+                    module: "swapping.wasm".into(),
+                }
+                .into())
+            } else {
+                Ok("readable again")
+            }
+        })
+        .await
+        .expect("succeeds once the module is readable");
+        assert_eq!(result, "readable again");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        let calls = AtomicU32::new(0);
+        let counter = &calls;
+        let err = retry_transient(no_timeouts, move || async move {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Err::<(), _>(PluginCallError::Timeout { timeout_ms: 10 }.into())
+        })
+        .await;
+        assert!(err.is_err());
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn in_flight_count_survives_limit_change() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let plugin = "limit-test-for-runtime";
+        let first = plugin_semaphore(plugin, 2);
+        let again = plugin_semaphore(plugin, 2);
+        assert!(Arc::ptr_eq(&first, &again));
+
+        let (started_tx, started_rx) = std::sync::mpsc::channel();
+        let (release_tx, release_rx) = std::sync::mpsc::channel::<()>();
+        let call = tokio::spawn(run_plugin_call(
+            Some(first),
+            in_flight_counter(plugin),
+            Arc::new(Semaphore::new(8)),
+            5_000,
+            move || {
+                started_tx.send(()).expect("signal start");
+                release_rx.recv().expect("wait for release");
+                Ok(())
+            },
+        ));
+        tokio::task::spawn_blocking(move || started_rx.recv())
+            .await
+            .expect("join")
+            .expect("call started");
+        assert_eq!(plugin_in_flight_calls().get(plugin), Some(&1));
+
+        let resized = plugin_semaphore(plugin, 1);
+        assert!(!Arc::ptr_eq(&again, &resized));
+        assert_eq!(plugin_in_flight_calls().get(plugin), Some(&1));
+
+        release_tx.send(()).expect("release call");
+        call.await.expect("join").expect("call succeeds");
+        assert_eq!(plugin_in_flight_calls().get(plugin), Some(&0));
+    }
+
+    #[tokio::test]
+    async fn timed_out_retry_waits_for_the_previous_guest() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "spin-retry.wasm", SPIN_WAT);
+        let settings = PluginSettings {
+            timeout_ms: Some(30),
+            ..PluginSettings::default()
+        };
+        let policy = CallPolicy {
+            retry: Some(RetryPolicy {
+                attempts: 2,
+                backoff_ms: 1,
+                retry_timeouts: true,
+            }),
+            ..CallPolicy::default()
+        };
+
+        let call = tokio::spawn(call_wasm_json_limited(
+            "spin-retry".to_string(),
+            module.clone(),
+            settings,
+            policy,
+            ABI_TOOL_EXEC_FN,
+            "{}".to_string(),
+        ));
+        let mut peak = 0;
+        while !call.is_finished() {
+            peak = peak.max(
+                plugin_in_flight_calls()
+                    .get("spin-retry")
+                    .copied()
+                    .unwrap_or_default(),
+            );
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+        let err = call.await.expect("join").unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::Timeout { timeout_ms: 30 })
+        );
+        assert_eq!(peak, 1, "attempts must not overlap");
+    }
+
+    #[test]
+    fn timeout_retries_require_an_idempotent_manifest() {
+        let entry = PluginEntryConfig {
+            retry_attempts: Some(2),
+            ..PluginEntryConfig::default()
+        };
+        let mut manifest = PluginManifest {
+            id: "retrying".into(),
+            ..PluginManifest::default()
+        };
+        admit_plugin(&manifest, Some(&entry)).expect("admit");
+        assert_eq!(
+            call_policy_for(&manifest, Some(&entry)).retry,
+            Some(RetryPolicy {
+                attempts: 2,
+                backoff_ms: DEFAULT_RETRY_BACKOFF_MS,
+                retry_timeouts: false,
+            })
+        );
+
+        manifest.idempotent = true;
+        assert_eq!(
+            call_policy_for(&manifest, Some(&entry))
+                .retry
+                .map(|retry| retry.retry_timeouts),
+            Some(true)
+        );
+    }
+
+    #[tokio::test]
+    async fn timed_out_call_holds_its_permits_until_the_guest_returns() {
+        let global = Arc::new(Semaphore::new(1));
+        let plugin = Arc::new(Semaphore::new(1));
+        let in_flight = Arc::new(AtomicUsize::new(0));
+        let slow_result = run_plugin_call(
+            Some(Arc::clone(&plugin)),
+            Arc::clone(&in_flight),
+            Arc::clone(&global),
+            10,
+            || -> anyhow::Result<&'static str> {
+                std::thread::sleep(std::time::Duration::from_millis(150));
+                Ok("slow")
+            },
+        )
+        .await;
+        assert_eq!(
+            slow_result.unwrap_err().downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::Timeout { timeout_ms: 10 })
+        );
+        assert_eq!(global.available_permits(), 0);
+        assert_eq!(plugin.available_permits(), 0);
+        assert_eq!(in_flight.load(Ordering::SeqCst), 1);
+
+        // A second call waits for the first guest instead of overlapping it.
+        let fast_result = run_plugin_call(
+            None,
+            Arc::new(AtomicUsize::new(0)),
+            Arc::clone(&global),
+            1_000,
+            || -> anyhow::Result<&'static str> { Ok("fast") },
+        )
+        .await
+        .expect("fast run should succeed");
+        assert_eq!(fast_result, "fast");
+        assert_eq!(plugin.available_permits(), 1);
+        assert_eq!(in_flight.load(Ordering::SeqCst), 0);
+        assert_eq!(global.available_permits(), 1);
+    }
+
+    #[tokio::test]
+    async fn waiting_for_permits_is_bounded_by_the_invoke_timeout() {
+        let global = Arc::new(Semaphore::new(1));
+        let plugin = Arc::new(Semaphore::new(1));
+        let call = |global: &Arc<Semaphore>, plugin: &Arc<Semaphore>| {
+            run_plugin_call(
+                Some(Arc::clone(plugin)),
+                Arc::new(AtomicUsize::new(0)),
+                Arc::clone(global),
+                20,
+                || -> anyhow::Result<()> { panic!("call must not run without its permits") },
+            )
+        };
+
+        // Queued behind the plugin's own limit, without holding a global slot.
+        let held = Arc::clone(&plugin).acquire_owned().await.expect("permit");
+        let err = call(&global, &plugin).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::Timeout { timeout_ms: 20 })
+        );
+        assert_eq!(global.available_permits(), 1);
+        drop(held);
+
+        // Queued behind the global limit; the plugin permit is given back.
+        let held = Arc::clone(&global).acquire_owned().await.expect("permit");
+        let err = call(&global, &plugin).await.unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::Timeout { timeout_ms: 20 })
+        );
+        assert_eq!(plugin.available_permits(), 1);
+        drop(held);
+    }
+
+    #[tokio::test]
+    async fn repeated_timeouts_quarantine_the_plugin_until_re_enabled() {
+        let _guard = crate::test_locks::PLUGIN_RUNTIME_LOCK.lock();
+        let dir = TempDir::new().expect("temp dir");
+        let module = write_wat_module(dir.path(), "runaway.wasm", SPIN_WAT);
+        let mut cfg = PluginsConfig {
+            enabled: true,
+            load_paths: vec![dir.path().to_string_lossy().to_string()],
+            ..PluginsConfig::default()
+        };
+        for (id, cooldown_secs) in [("runaway", None), ("cooling", Some(1))] {
+            std::fs::write(
+                dir.path().join(format!("{id}.plugin.toml")),
+                format!(
+                    r#"
+id = "{id}"
+version = "1.0.0"
+module_path = "{module}"
+wit_packages = ["zeroclaw:tools@1.0.0"]
+
+[[tools]]
+name = "{id}_tool"
+description = "runaway tool"
+"#
+                ),
+            )
+            .expect("write manifest");
+            cfg.entries.insert(
+                id.into(),
+                PluginEntryConfig {
+                    timeout_ms: Some(20),
+                    quarantine_after_timeouts: Some(3),
+                    quarantine_window_secs: Some(60),
+                    quarantine_cooldown_secs: cooldown_secs,
+                    ..PluginEntryConfig::default()
+                },
+            );
+        }
+        initialize_from_config(&cfg).expect("initialize");
+
+        for id in ["runaway", "cooling"] {
+            let tool = format!("{id}_tool");
+            for _ in 0..3 {
+                let err = execute_plugin_tool(&tool, &serde_json::json!({}))
+                    .await
+                    .unwrap_err();
+                assert_eq!(
+                    err.downcast_ref::<PluginCallError>(),
+                    Some(&PluginCallError::Timeout { timeout_ms: 20 })
+                );
+            }
+            assert!(current_registry().is_quarantined(id));
+            let err = execute_plugin_tool(&tool, &serde_json::json!({}))
+                .await
+                .unwrap_err();
+            assert!(err.to_string().contains("not found in registry"), "{err}");
+        }
+
+        // Without a cooldown the plugin stays out until re-enabled.
+        assert!(reenable_plugin("runaway").expect("re-enable"));
+        assert!(!reenable_plugin("runaway").expect("already enabled"));
+        assert!(reenable_plugin("missing").is_err());
+        let err = execute_plugin_tool("runaway_tool", &serde_json::json!({}))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<PluginCallError>(),
+            Some(&PluginCallError::Timeout { timeout_ms: 20 })
+        );
+        assert!(
+            !current_registry().is_quarantined("runaway"),
+            "re-enabling forgets earlier timeouts"
+        );
+
+        tokio::time::sleep(Duration::from_millis(1_100)).await;
+        assert!(!current_registry().is_quarantined("cooling"));
+        assert_eq!(
+            current_registry().tool_plugin_id("cooling_tool"),
+            Some("cooling")
+        );
     }
 }
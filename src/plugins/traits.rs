@@ -18,6 +18,9 @@ pub enum PluginCapability {
     Providers,
     /// Permission to modify tool results via the `tool_result_persist` hook.
     ModifyToolResults,
+    /// Serves a memory backend through `zeroclaw_memory_call`, usable as a
+    /// `plugin:<id>` memory tier.
+    Memory,
 }
 
 /// Context passed to a plugin during registration.
@@ -136,6 +139,7 @@ mod tests {
                 wit_packages: vec![],
                 tools: vec![],
                 providers: vec![],
+                idempotent: false,
             },
         };
         let mut api = PluginApi {